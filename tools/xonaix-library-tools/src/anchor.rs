@@ -0,0 +1,784 @@
+//! ZeroPoint anchoring module.
+//!
+//! Batches sealed documents into ledger blocks and records the resulting
+//! `zeropoint_anchor` section in each header, as described in
+//! ZEROPOINT_INTEGRATION_SPEC §4 and §5.2. Anchors are verified offline
+//! against the Merkle proof in the header and the ledger's block chain.
+//!
+//! Until the ZeroPoint ledger exists, blocks are kept in a local append-only
+//! JSONL file ledger committed under `specs/_governance/manifests/anchors/`.
+//! The ledger sits behind [`LedgerBackend`] so the real service can replace
+//! [`FileLedger`] without touching the workflow.
+//!
+//! Each block links to the previous block through `prev_hash`, and each
+//! anchor records the hash of its own block in `block_hash`, so rewriting
+//! any block, including the last, breaks the anchors that depend on it.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+use crate::canonical;
+use crate::common::{collect_documents, extract_frontmatter, find_repo_root, get_yaml_string, relative_path};
+use crate::merkle;
+use crate::seal;
+
+/// Ledger used when none is given.
+pub(crate) const DEFAULT_LEDGER_ID: &str = "xzp-local";
+
+/// Repository-relative directory holding local file ledgers.
+const LEDGER_DIR: &str = "specs/_governance/manifests/anchors";
+
+/// Header comment line the anchor section is inserted before.
+const LIFECYCLE_SECTION: &str = "# --- Lifecycle ---";
+
+/// Errors during anchoring.
+#[derive(Debug, Error)]
+pub enum AnchorError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error(transparent)]
+    InvalidHash(#[from] merkle::InvalidHash),
+
+    #[error("Cannot find specs/ directory")]
+    NoSpecsDir,
+
+    #[error("{0}: missing or invalid YAML frontmatter")]
+    InvalidHeader(PathBuf),
+
+    #[error("Invalid ledger_id '{0}' (expected lowercase letters, digits and '-')")]
+    InvalidLedgerId(String),
+
+    #[error("Ledger {0} is corrupt at line {1}: {2}")]
+    CorruptLedger(PathBuf, usize, String),
+
+    #[error("Anchoring refused: {0} document(s) cannot be anchored")]
+    NotAnchorable(u32),
+
+    #[error("Anchor verification failed: {0} document(s) failed")]
+    VerificationFailed(u32),
+}
+
+/// A document recorded in a block.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct BlockEntry {
+    pub content_hash: String,
+    pub path: String,
+}
+
+/// One ledger block.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct Block {
+    pub block_height: u64,
+    pub documents: Vec<BlockEntry>,
+    /// Merkle root over the documents' content hashes.
+    pub merkle_root: String,
+    /// Hash of the previous block; all zeros for the first block.
+    pub prev_hash: String,
+    pub timestamp: String,
+}
+
+impl Block {
+    /// SHA3-512 over the block's canonical JSON.
+    pub(crate) fn hash(&self) -> Result<String, serde_json::Error> {
+        canonical::canonical_hash(&serde_json::to_string(self)?)
+    }
+}
+
+/// `prev_hash` of the first block.
+fn genesis_prev_hash() -> String {
+    "0".repeat(128)
+}
+
+/// Storage for anchor blocks.
+pub(crate) trait LedgerBackend {
+    /// Identifier recorded in `zeropoint_anchor.ledger_id`.
+    fn ledger_id(&self) -> &str;
+
+    /// Whether the ledger can be read at all.
+    fn is_available(&self) -> bool;
+
+    /// All blocks, in height order.
+    fn blocks(&self) -> Result<Vec<Block>, AnchorError>;
+
+    /// The block holding `documents` that would extend the chain, not yet appended.
+    fn next_block(&self, documents: Vec<BlockEntry>, timestamp: String) -> Result<Block, AnchorError> {
+        let (block_height, prev_hash) = match self.blocks()?.last() {
+            Some(last) => (last.block_height + 1, last.hash()?),
+            None => (1, genesis_prev_hash()),
+        };
+
+        let hashes: Vec<&str> = documents.iter().map(|d| d.content_hash.as_str()).collect();
+        Ok(Block {
+            block_height,
+            merkle_root: merkle::root(&hashes)?,
+            documents,
+            prev_hash,
+            timestamp,
+        })
+    }
+
+    /// Append a block built by [`next_block`](Self::next_block).
+    fn append(&mut self, block: &Block) -> Result<(), AnchorError>;
+}
+
+/// Whether `ledger_id` is safe to use as a ledger file name: `[a-z0-9-]+`.
+fn is_valid_ledger_id(ledger_id: &str) -> bool {
+    !ledger_id.is_empty() && ledger_id.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-')
+}
+
+/// Local append-only ledger: one canonical JSON block per line.
+pub(crate) struct FileLedger {
+    ledger_id: String,
+    path: PathBuf,
+}
+
+impl FileLedger {
+    /// Open a ledger at `path`, or at the default location for `ledger_id`.
+    ///
+    /// `ledger_id` comes from document headers, so it is checked before it
+    /// becomes part of a path.
+    pub(crate) fn open(repo_root: &Path, ledger_id: &str, path: Option<&Path>) -> Result<Self, AnchorError> {
+        if !is_valid_ledger_id(ledger_id) {
+            return Err(AnchorError::InvalidLedgerId(ledger_id.to_string()));
+        }
+        let path = match path {
+            Some(p) => p.to_path_buf(),
+            None => repo_root.join(LEDGER_DIR).join(format!("{}.jsonl", ledger_id)),
+        };
+        Ok(FileLedger {
+            ledger_id: ledger_id.to_string(),
+            path,
+        })
+    }
+}
+
+impl LedgerBackend for FileLedger {
+    fn ledger_id(&self) -> &str {
+        &self.ledger_id
+    }
+
+    fn is_available(&self) -> bool {
+        self.path.exists()
+    }
+
+    fn blocks(&self) -> Result<Vec<Block>, AnchorError> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+
+        fs::read_to_string(&self.path)?
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                serde_json::from_str(line).map_err(|e| AnchorError::CorruptLedger(self.path.clone(), i + 1, e.to_string()))
+            })
+            .collect()
+    }
+
+    fn append(&mut self, block: &Block) -> Result<(), AnchorError> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{}", canonical::to_canonical_string(block)?)?;
+
+        Ok(())
+    }
+}
+
+/// Check block heights, `prev_hash` links, timestamps and Merkle roots across a ledger.
+pub(crate) fn verify_chain(blocks: &[Block]) -> Result<Vec<String>, AnchorError> {
+    let mut problems = Vec::new();
+    let mut prev_hash = genesis_prev_hash();
+    let mut prev_time: Option<DateTime<Utc>> = None;
+
+    for (i, block) in blocks.iter().enumerate() {
+        let expected_height = i as u64 + 1;
+        if block.block_height != expected_height {
+            problems.push(format!(
+                "block {}: expected height {}",
+                block.block_height, expected_height
+            ));
+        }
+        if block.prev_hash != prev_hash {
+            problems.push(format!("block {}: prev_hash does not link to the previous block", block.block_height));
+        }
+        let hashes: Vec<&str> = block.documents.iter().map(|d| d.content_hash.as_str()).collect();
+        if !merkle::root(&hashes)?.eq_ignore_ascii_case(&block.merkle_root) {
+            problems.push(format!("block {}: merkle_root does not match its documents", block.block_height));
+        }
+        match DateTime::parse_from_rfc3339(&block.timestamp) {
+            Ok(time) => {
+                let time = time.with_timezone(&Utc);
+                if prev_time.is_some_and(|prev| time < prev) {
+                    problems.push(format!("block {}: timestamp is before the previous block", block.block_height));
+                }
+                prev_time = Some(time);
+            }
+            Err(_) => problems.push(format!("block {}: invalid timestamp {}", block.block_height, block.timestamp)),
+        }
+        prev_hash = block.hash()?;
+    }
+
+    Ok(problems)
+}
+
+/// The `zeropoint_anchor` header section.
+#[derive(Debug, Deserialize)]
+pub(crate) struct ZeropointAnchor {
+    pub ledger_id: Option<String>,
+    pub block_height: Option<u64>,
+    /// Hash of the block recording the document.
+    pub block_hash: Option<String>,
+    pub merkle_root: Option<String>,
+    #[serde(default)]
+    pub merkle_proof: Option<Vec<String>>,
+    pub anchored_at: Option<String>,
+    pub anchor_ref: Option<String>,
+}
+
+impl ZeropointAnchor {
+    /// Whether any anchor field has been filled in.
+    pub(crate) fn is_populated(&self) -> bool {
+        self.ledger_id.is_some()
+            || self.block_height.is_some()
+            || self.block_hash.is_some()
+            || self.merkle_root.is_some()
+            || self.anchor_ref.is_some()
+    }
+}
+
+/// Header fields needed for anchoring.
+#[derive(Debug, Deserialize)]
+struct AnchorHeader {
+    status: Option<String>,
+    integrity: Option<AnchorIntegrity>,
+    zeropoint_anchor: Option<ZeropointAnchor>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnchorIntegrity {
+    content_hash: Option<serde_yaml::Value>,
+    signature: Option<serde_yaml::Value>,
+}
+
+/// Reference to a document in a ledger block: `xzp://<ledger>/<height>/doc/<content_hash>`.
+pub(crate) fn anchor_ref(ledger_id: &str, block_height: u64, content_hash: &str) -> String {
+    let ledger = ledger_id.strip_prefix("xzp-").unwrap_or(ledger_id);
+    format!("xzp://{}/{}/doc/{}", ledger, block_height, content_hash)
+}
+
+/// Split an anchor reference into its ledger, block height and content hash.
+///
+/// Returns `None` unless the reference has the form
+/// `xzp://<ledger>/<height>/doc/<content_hash>`.
+pub(crate) fn parse_anchor_ref(anchor_ref: &str) -> Option<(&str, u64, &str)> {
+    let rest = anchor_ref.strip_prefix("xzp://")?;
+    let mut parts = rest.split('/');
+    let ledger = parts.next().filter(|l| !l.is_empty())?;
+    let height = parts.next()?.parse().ok()?;
+    if parts.next()? != "doc" {
+        return None;
+    }
+    let hash = parts.next().filter(|h| !h.is_empty() && h.chars().all(|c| c.is_ascii_hexdigit()))?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((ledger, height, hash))
+}
+
+/// Check an anchor section against a content hash and the ledger.
+///
+/// The ledger must be available, its chain must verify and the anchored
+/// block must be present with the recorded `block_hash`. Returns the
+/// problems found; an empty list means the anchor verified.
+pub(crate) fn verify_anchor(
+    anchor: &ZeropointAnchor,
+    content_hash: &str,
+    ledger: &dyn LedgerBackend,
+) -> Result<Vec<String>, AnchorError> {
+    let mut problems = Vec::new();
+
+    let (ledger_id, block_height, block_hash, merkle_root) =
+        match (&anchor.ledger_id, anchor.block_height, &anchor.block_hash, &anchor.merkle_root) {
+            (Some(id), Some(height), Some(hash), Some(root)) => (id, height, hash, root),
+            _ => {
+                problems.push(
+                    "zeropoint_anchor is incomplete (ledger_id, block_height, block_hash and merkle_root are required)"
+                        .to_string(),
+                );
+                return Ok(problems);
+            }
+        };
+
+    let proof = anchor.merkle_proof.clone().unwrap_or_default();
+    if !merkle::walk(content_hash, &proof)?.eq_ignore_ascii_case(merkle_root) {
+        problems.push("merkle_proof does not lead from content_hash to merkle_root".to_string());
+    }
+
+    let expected_ref = anchor_ref(ledger_id, block_height, content_hash);
+    match anchor.anchor_ref.as_deref() {
+        None => problems.push("zeropoint_anchor.anchor_ref is missing".to_string()),
+        Some(r) if parse_anchor_ref(r).is_none() => {
+            problems.push(format!("anchor_ref is not of the form xzp://<ledger>/<height>/doc/<hash>: {}", r));
+        }
+        Some(r) if r != expected_ref => problems.push(format!("anchor_ref should be {}", expected_ref)),
+        Some(_) => {}
+    }
+
+    if !ledger.is_available() {
+        problems.push(format!("ledger {} not available", ledger_id));
+        return Ok(problems);
+    }
+
+    let blocks = ledger.blocks()?;
+    if !verify_chain(&blocks)?.is_empty() {
+        problems.push(format!("ledger {} chain does not verify", ledger_id));
+    }
+    match blocks.iter().find(|b| b.block_height == block_height) {
+        None => problems.push(format!("block {} not found in ledger {}", block_height, ledger_id)),
+        Some(block) => {
+            if !block.hash()?.eq_ignore_ascii_case(block_hash) {
+                problems.push(format!("block_hash differs from block {}", block_height));
+            }
+            if !block.merkle_root.eq_ignore_ascii_case(merkle_root) {
+                problems.push(format!("merkle_root differs from block {}", block_height));
+            }
+            if !block.documents.iter().any(|d| d.content_hash.eq_ignore_ascii_case(content_hash)) {
+                problems.push(format!("content_hash not recorded in block {}", block_height));
+            }
+            if anchor.anchored_at.as_deref() != Some(block.timestamp.as_str()) {
+                problems.push(format!("anchored_at differs from block {} timestamp", block_height));
+            }
+        }
+    }
+
+    Ok(problems)
+}
+
+/// Check an anchor section against a content hash and the repository's ledger.
+pub(crate) fn verify_document_anchor(
+    repo_root: &Path,
+    anchor: &ZeropointAnchor,
+    content_hash: &str,
+) -> Result<Vec<String>, AnchorError> {
+    let ledger = FileLedger::open(repo_root, anchor.ledger_id.as_deref().unwrap_or_default(), None)?;
+    verify_anchor(anchor, content_hash, &ledger)
+}
+
+/// Parse a document header.
+fn parse_header(path: &Path, content: &str) -> Result<AnchorHeader, AnchorError> {
+    let frontmatter = extract_frontmatter(content).ok_or_else(|| AnchorError::InvalidHeader(path.to_path_buf()))?;
+    serde_yaml::from_str(frontmatter).map_err(|_| AnchorError::InvalidHeader(path.to_path_buf()))
+}
+
+/// Render the `zeropoint_anchor` section for one document.
+fn anchor_section(block: &Block, block_hash: &str, ledger_id: &str, content_hash: &str, proof: &[String]) -> Vec<String> {
+    let mut lines = vec![
+        "# --- ZeroPoint Anchor ---".to_string(),
+        "zeropoint_anchor:".to_string(),
+        format!("  ledger_id: {}", seal::yaml_quote(ledger_id)),
+        format!("  block_height: {}", block.block_height),
+        format!("  block_hash: {}", seal::yaml_quote(block_hash)),
+        format!("  merkle_root: {}", seal::yaml_quote(&block.merkle_root)),
+    ];
+    if proof.is_empty() {
+        lines.push("  merkle_proof: []".to_string());
+    } else {
+        lines.push("  merkle_proof:".to_string());
+        lines.extend(proof.iter().map(|p| format!("    - {}", seal::yaml_quote(p))));
+    }
+    lines.push(format!("  anchored_at: {}", seal::yaml_quote(&block.timestamp)));
+    lines.push(format!(
+        "  anchor_ref: {}",
+        seal::yaml_quote(&anchor_ref(ledger_id, block.block_height, content_hash))
+    ));
+    lines
+}
+
+/// Run the `anchor submit` command.
+///
+/// Batches every sealed, not yet anchored document into one new block and
+/// writes the anchor section back into each header.
+pub fn submit(
+    repo_root_arg: Option<String>,
+    file_path: Option<String>,
+    ledger_id: Option<String>,
+    ledger_path: Option<String>,
+) -> Result<(), AnchorError> {
+    let repo_root = match repo_root_arg {
+        Some(path) => PathBuf::from(path),
+        None => find_repo_root()?.ok_or(AnchorError::NoSpecsDir)?,
+    };
+    let ledger_id = ledger_id.unwrap_or_else(|| DEFAULT_LEDGER_ID.to_string());
+    let mut ledger = FileLedger::open(&repo_root, &ledger_id, ledger_path.as_deref().map(Path::new))?;
+
+    println!("=== XONAIX ZEROPOINT ANCHOR SUBMIT ===");
+    println!("Repository: {}", repo_root.display());
+    println!("Ledger: {}", ledger.ledger_id());
+    println!();
+
+    let mut batch: Vec<(PathBuf, String, String)> = Vec::new();
+    let mut refused = 0u32;
+
+    for path in collect_documents(&repo_root, file_path)? {
+        let content = fs::read_to_string(&path)?;
+        let header = parse_header(&path, &content)?;
+
+        if header.status.as_deref() != Some("sealed") {
+            continue;
+        }
+        if header.zeropoint_anchor.as_ref().is_some_and(ZeropointAnchor::is_populated) {
+            println!("SKIP: {} (already anchored)", path.display());
+            continue;
+        }
+
+        let integrity = header.integrity.as_ref();
+        let stored = integrity.and_then(|i| get_yaml_string(&i.content_hash));
+        let signed = integrity.and_then(|i| get_yaml_string(&i.signature)).is_some();
+        let computed = seal::compute_content_hash(&content);
+
+        match (stored, computed) {
+            (Some(stored), Some(computed)) if signed && stored.eq_ignore_ascii_case(&computed) => {
+                batch.push((path.clone(), content, computed));
+            }
+            (Some(_), Some(_)) if signed => {
+                println!("FAIL: {}: content_hash does not match document body", path.display());
+                refused += 1;
+            }
+            _ => {
+                println!("FAIL: {}: sealed without content_hash and signature", path.display());
+                refused += 1;
+            }
+        }
+    }
+
+    if refused > 0 {
+        return Err(AnchorError::NotAnchorable(refused));
+    }
+    if batch.is_empty() {
+        println!("Nothing to anchor.");
+        return Ok(());
+    }
+
+    let entries: Vec<BlockEntry> = batch
+        .iter()
+        .map(|(path, _, hash)| BlockEntry {
+            content_hash: hash.clone(),
+            path: relative_path(path, &repo_root),
+        })
+        .collect();
+    let hashes: Vec<String> = entries.iter().map(|e| e.content_hash.clone()).collect();
+    let hash_refs: Vec<&str> = hashes.iter().map(String::as_str).collect();
+
+    let block = ledger.next_block(entries, Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string())?;
+    let block_hash = block.hash()?;
+
+    // Every header must take its anchor before the block is committed, so a
+    // bad header cannot leave a block whose documents were never anchored
+    let mut updates = Vec::new();
+    for (path, content, hash) in &batch {
+        let proof = merkle::prove(&hash_refs, hash)?.unwrap_or_default();
+        let section = anchor_section(&block, &block_hash, &ledger_id, hash, &proof);
+        let updated =
+            seal::insert_section(content, &section, LIFECYCLE_SECTION).ok_or_else(|| AnchorError::InvalidHeader(path.clone()))?;
+        let anchored = parse_header(path, &updated)?.zeropoint_anchor.is_some_and(|a| a.is_populated());
+        if !anchored || seal::compute_content_hash(&updated).as_ref() != Some(hash) {
+            return Err(AnchorError::InvalidHeader(path.clone()));
+        }
+        updates.push((path, hash, updated));
+    }
+
+    ledger.append(&block)?;
+
+    for (path, hash, updated) in updates {
+        fs::write(path, updated)?;
+        println!("ANCHORED: {} ({}...)", path.display(), &hash[..16]);
+    }
+
+    println!();
+    println!("=== ANCHOR SUBMIT COMPLETE ===");
+    println!("Block Height: {}", block.block_height);
+    println!("Merkle Root: {}...", &block.merkle_root[..16]);
+    println!("Documents: {}", batch.len());
+
+    Ok(())
+}
+
+/// Run the `anchor verify` command.
+///
+/// Verifies every anchored document against its ledger block, and the
+/// integrity of each ledger chain. A ledger that cannot be found fails.
+pub fn verify(
+    repo_root_arg: Option<String>,
+    file_path: Option<String>,
+    ledger_path: Option<String>,
+) -> Result<(), AnchorError> {
+    let repo_root = match repo_root_arg {
+        Some(path) => PathBuf::from(path),
+        None => find_repo_root()?.ok_or(AnchorError::NoSpecsDir)?,
+    };
+
+    println!("=== XONAIX ZEROPOINT ANCHOR VERIFICATION ===");
+    println!("Repository: {}", repo_root.display());
+    println!();
+
+    let mut verified = 0u32;
+    let mut failed = 0u32;
+    let mut checked_ledgers: Vec<String> = Vec::new();
+
+    for path in collect_documents(&repo_root, file_path)? {
+        let content = fs::read_to_string(&path)?;
+        let header = parse_header(&path, &content)?;
+
+        let anchor = match header.zeropoint_anchor {
+            Some(anchor) if anchor.is_populated() => anchor,
+            _ => continue,
+        };
+
+        let computed = seal::compute_content_hash(&content).ok_or_else(|| AnchorError::InvalidHeader(path.clone()))?;
+        let stored = header.integrity.as_ref().and_then(|i| get_yaml_string(&i.content_hash));
+
+        let mut problems = Vec::new();
+        if !stored.is_some_and(|s| s.eq_ignore_ascii_case(&computed)) {
+            problems.push("content_hash does not match document body".to_string());
+        }
+
+        let ledger_id = anchor.ledger_id.clone().unwrap_or_default();
+        let ledger = match FileLedger::open(&repo_root, &ledger_id, ledger_path.as_deref().map(Path::new)) {
+            Ok(ledger) => ledger,
+            Err(e) => {
+                println!("FAIL: {}: {}", path.display(), e);
+                failed += 1;
+                continue;
+            }
+        };
+        if !checked_ledgers.contains(&ledger_id) {
+            checked_ledgers.push(ledger_id.clone());
+            if ledger.is_available() {
+                for problem in verify_chain(&ledger.blocks()?)? {
+                    println!("FAIL: ledger {}: {}", ledger_id, problem);
+                    failed += 1;
+                }
+            } else {
+                println!("FAIL: ledger {} not available", ledger_id);
+                failed += 1;
+            }
+        }
+
+        problems.extend(verify_anchor(&anchor, &computed, &ledger)?);
+
+        if problems.is_empty() {
+            println!("PASS: {} (block {})", path.display(), anchor.block_height.unwrap_or_default());
+            verified += 1;
+        } else {
+            for problem in &problems {
+                println!("FAIL: {}: {}", path.display(), problem);
+            }
+            failed += 1;
+        }
+    }
+
+    println!();
+    println!("=== ANCHOR VERIFICATION COMPLETE ===");
+    println!("Verified: {}", verified);
+    println!("Failed: {}", failed);
+
+    if failed > 0 {
+        Err(AnchorError::VerificationFailed(failed))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    const HASH_A: &str = "aa";
+    const HASH_B: &str = "bb";
+
+    fn temp_ledger(name: &str) -> FileLedger {
+        let path = std::env::temp_dir().join(format!("xlib-anchor-{}-{}.jsonl", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        FileLedger {
+            ledger_id: DEFAULT_LEDGER_ID.to_string(),
+            path,
+        }
+    }
+
+    /// Build and append the next block holding `documents`.
+    pub(crate) fn push_block(
+        ledger: &mut impl LedgerBackend,
+        documents: Vec<BlockEntry>,
+        timestamp: String,
+    ) -> Result<Block, AnchorError> {
+        let block = ledger.next_block(documents, timestamp)?;
+        ledger.append(&block)?;
+        Ok(block)
+    }
+
+    fn entry(hash: &str) -> BlockEntry {
+        BlockEntry {
+            content_hash: hash.to_string(),
+            path: format!("specs/{}.md", hash),
+        }
+    }
+
+    /// The anchor `submit` would write for `hash` in `block`.
+    fn anchor_for(block: &Block, hashes: &[&str], hash: &str) -> ZeropointAnchor {
+        ZeropointAnchor {
+            ledger_id: Some(DEFAULT_LEDGER_ID.to_string()),
+            block_height: Some(block.block_height),
+            block_hash: block.hash().ok(),
+            merkle_root: Some(block.merkle_root.clone()),
+            merkle_proof: merkle::prove(hashes, hash).ok().flatten(),
+            anchored_at: Some(block.timestamp.clone()),
+            anchor_ref: Some(anchor_ref(DEFAULT_LEDGER_ID, block.block_height, hash)),
+        }
+    }
+
+    #[test]
+    fn anchor_verifies_against_its_block() {
+        let mut ledger = temp_ledger("valid");
+        let block = push_block(&mut ledger, vec![entry(HASH_A), entry(HASH_B)], "2026-01-01T00:00:00Z".to_string());
+        let anchor = block.as_ref().map(|b| anchor_for(b, &[HASH_A, HASH_B], HASH_A));
+        let result = anchor.map(|a| verify_anchor(&a, HASH_A, &ledger));
+        assert!(matches!(result, Ok(Ok(problems)) if problems.is_empty()));
+        let _ = fs::remove_file(&ledger.path);
+    }
+
+    #[test]
+    fn forged_single_document_anchor_needs_the_ledger() {
+        // A one-leaf tree has root SHA3(0x00 || hash) and an empty proof,
+        // so the header alone can always be made self-consistent.
+        let forged = ZeropointAnchor {
+            ledger_id: Some(DEFAULT_LEDGER_ID.to_string()),
+            block_height: Some(1),
+            block_hash: Some("00".repeat(64)),
+            merkle_root: merkle::root(&[HASH_A]).ok(),
+            merkle_proof: Some(Vec::new()),
+            anchored_at: Some("2020-01-01T00:00:00Z".to_string()),
+            anchor_ref: Some(anchor_ref(DEFAULT_LEDGER_ID, 1, HASH_A)),
+        };
+        let ledger = temp_ledger("missing");
+        let result = verify_anchor(&forged, HASH_A, &ledger);
+        assert!(matches!(result, Ok(problems) if problems.iter().any(|p| p.contains("not available"))));
+
+        let mut ledger = temp_ledger("other");
+        let _ = push_block(&mut ledger, vec![entry(HASH_B)], "2026-01-01T00:00:00Z".to_string());
+        let result = verify_anchor(&forged, HASH_A, &ledger);
+        assert!(matches!(result, Ok(problems) if !problems.is_empty()));
+        let _ = fs::remove_file(&ledger.path);
+    }
+
+    #[test]
+    fn rewritten_last_block_fails() {
+        let mut ledger = temp_ledger("rewrite");
+        let _ = push_block(&mut ledger, vec![entry(HASH_B)], "2026-01-01T00:00:00Z".to_string());
+        let block = push_block(&mut ledger, vec![entry(HASH_A)], "2026-02-01T00:00:00Z".to_string());
+        let anchor = block.as_ref().map(|b| anchor_for(b, &[HASH_A], HASH_A));
+
+        // Backdate the last block and rewrite it consistently
+        let blocks = ledger.blocks().unwrap_or_default();
+        let rewritten: Vec<String> = blocks
+            .into_iter()
+            .map(|mut b| {
+                if b.block_height == 2 {
+                    b.timestamp = "2026-01-15T00:00:00Z".to_string();
+                }
+                canonical::to_canonical_string(&b).unwrap_or_default()
+            })
+            .collect();
+        assert!(fs::write(&ledger.path, rewritten.join("\n") + "\n").is_ok());
+        assert!(ledger.blocks().is_ok_and(|b| verify_chain(&b).is_ok_and(|p| p.is_empty())));
+
+        let result = anchor.map(|mut a| {
+            a.anchored_at = Some("2026-01-15T00:00:00Z".to_string());
+            verify_anchor(&a, HASH_A, &ledger)
+        });
+        assert!(matches!(result, Ok(Ok(problems)) if problems.iter().any(|p| p.contains("block_hash"))));
+        let _ = fs::remove_file(&ledger.path);
+    }
+
+    #[test]
+    fn chain_detects_broken_links_and_time_order() {
+        let mut ledger = temp_ledger("chain");
+        let _ = push_block(&mut ledger, vec![entry(HASH_A)], "2026-02-01T00:00:00Z".to_string());
+        let _ = push_block(&mut ledger, vec![entry(HASH_B)], "2026-01-01T00:00:00Z".to_string());
+        let mut blocks = ledger.blocks().unwrap_or_default();
+        let problems = verify_chain(&blocks).unwrap_or_default();
+        assert!(problems.iter().any(|p| p.contains("timestamp is before")));
+
+        if let Some(first) = blocks.first_mut() {
+            first.documents.clear();
+        }
+        let problems = verify_chain(&blocks).unwrap_or_default();
+        assert!(problems.iter().any(|p| p.contains("merkle_root")));
+        assert!(problems.iter().any(|p| p.contains("prev_hash")));
+        let _ = fs::remove_file(&ledger.path);
+    }
+
+    #[test]
+    fn ledger_ids_cannot_leave_the_ledger_directory() {
+        let repo_root = Path::new("/repo");
+        assert!(FileLedger::open(repo_root, "xzp-local-2", None).is_ok());
+        for ledger_id in ["", "../../../etc/passwd", "xzp/local", "XZP", "xzp.local", "/tmp/x"] {
+            let result = FileLedger::open(repo_root, ledger_id, None);
+            assert!(matches!(result, Err(AnchorError::InvalidLedgerId(id)) if id == ledger_id));
+        }
+
+        let anchor = ZeropointAnchor {
+            ledger_id: Some("../outside".to_string()),
+            block_height: Some(1),
+            block_hash: None,
+            merkle_root: None,
+            merkle_proof: None,
+            anchored_at: None,
+            anchor_ref: None,
+        };
+        let result = verify_document_anchor(repo_root, &anchor, HASH_A);
+        assert!(matches!(result, Err(AnchorError::InvalidLedgerId(_))));
+    }
+
+    /// Regression: a header that could not take its anchor was found only after the block was appended.
+    #[test]
+    fn submit_checks_every_header_before_appending_the_block() {
+        let repo_root = std::env::temp_dir().join(format!("xlib-anchor-submit-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&repo_root);
+        assert!(fs::create_dir_all(repo_root.join("specs")).is_ok());
+
+        let body = "Sealed body.\n";
+        let hash = seal::compute_content_hash(&format!("---\n---\n{}", body)).unwrap_or_default();
+        let sealed = format!("---\nstatus: \"sealed\"\nintegrity:\n  content_hash: \"{}\"\n  signature: \"sig\"\n", hash);
+        let good = format!("{}---\n{}", sealed, body);
+        // An empty placeholder section would become a duplicate key
+        let bad = format!("{}zeropoint_anchor:\n  ledger_id: null\n---\n{}", sealed, body);
+        assert!(fs::write(repo_root.join("specs").join("A.md"), &good).is_ok());
+        assert!(fs::write(repo_root.join("specs").join("B.md"), &bad).is_ok());
+
+        let result = submit(Some(repo_root.display().to_string()), None, None, None);
+        assert!(matches!(result, Err(AnchorError::InvalidHeader(_))));
+        assert!(!repo_root.join(LEDGER_DIR).exists());
+        assert_eq!(fs::read_to_string(repo_root.join("specs").join("A.md")).unwrap_or_default(), good);
+        let _ = fs::remove_dir_all(&repo_root);
+    }
+
+    #[test]
+    fn anchor_refs_round_trip() {
+        let reference = anchor_ref("xzp-main", 12, "ab12");
+        assert_eq!(reference, "xzp://main/12/doc/ab12");
+        assert_eq!(parse_anchor_ref(&reference), Some(("main", 12, "ab12")));
+        assert_eq!(parse_anchor_ref("xzp://main/12/doc/zz"), None);
+        assert_eq!(parse_anchor_ref("xzp://main/12/doc/ab/extra"), None);
+    }
+}
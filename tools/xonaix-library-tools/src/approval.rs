@@ -0,0 +1,367 @@
+//! Multi-party approval module.
+//!
+//! Evaluates the v3.0 `approvals` and `approval_requirements` header blocks
+//! described in MULTI_PARTY_GOVERNANCE_SPEC §3–§5.
+//!
+//! Approval signatures form a chain: each signature covers the document's
+//! content hash, the approval's party, role, signer and time, and every
+//! signature that precedes it in the `approvals` list, so no earlier approval
+//! can be removed or altered without breaking the ones after it.
+//!
+//! The keyring ties each signer to its party and the roles it may approve
+//! as, and a document without a loadable keyring has no valid approvals.
+
+use chrono::{DateTime, Utc};
+use ed25519_dalek::Signature;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+use crate::canonical;
+use crate::keyring::Keyring;
+
+/// Domain separator of approval signatures.
+const APPROVAL_DOMAIN: &str = "xonaix-approval-signature/v1";
+
+/// Valid approval statuses (MULTI_PARTY_GOVERNANCE_SPEC §4).
+const VALID_APPROVAL_STATUSES: &[&str] = &["pending", "in_review", "approved", "sealed", "rejected", "withdrawn"];
+
+/// One entry of the `approvals` list.
+#[derive(Debug, Deserialize)]
+pub(crate) struct Approval {
+    pub party: Option<String>,
+    pub role: Option<String>,
+    pub status: Option<String>,
+    pub approved_by: Option<String>,
+    pub approved_at: Option<String>,
+    pub signature: Option<String>,
+}
+
+/// The `approval_requirements` section.
+#[derive(Debug, Deserialize)]
+pub(crate) struct ApprovalRequirements {
+    pub minimum_approvals: Option<u32>,
+    #[serde(default)]
+    pub required_roles: Vec<String>,
+    #[serde(default)]
+    pub optional_roles: Vec<String>,
+}
+
+impl ApprovalRequirements {
+    /// Whether `role` is one of the required or optional roles.
+    fn allows_role(&self, role: &str) -> bool {
+        self.required_roles.iter().chain(&self.optional_roles).any(|r| r == role)
+    }
+}
+
+/// Outcome of evaluating a document's approvals.
+#[derive(Debug, Default)]
+pub(crate) struct ApprovalEvaluation {
+    /// Malformed entries and signatures that cannot be verified.
+    pub errors: Vec<String>,
+    /// Requirements that are not yet satisfied.
+    pub unmet: Vec<String>,
+    /// Parties with at least one verified approval.
+    pub signed_parties: Vec<String>,
+    /// Roles with at least one verified approval.
+    pub signed_roles: Vec<String>,
+}
+
+/// Statement signed by an approval.
+#[derive(Debug, Serialize)]
+struct ApprovalPayload<'a> {
+    approved_at: &'a str,
+    approved_by: &'a str,
+    content_hash: &'a str,
+    domain: &'a str,
+    party: &'a str,
+    prior_signatures: &'a [String],
+    role: &'a str,
+}
+
+/// Fields of an approval that its signature covers.
+pub(crate) struct ApprovalStatement<'a> {
+    pub party: &'a str,
+    pub role: &'a str,
+    pub approved_by: &'a str,
+    pub approved_at: &'a str,
+}
+
+/// Bytes signed by an approval: the canonical JSON of the content hash, the
+/// approval's party, role, signer and time, and all prior signatures (hex),
+/// under the approval signature domain.
+pub(crate) fn chain_message(
+    content_hash: &str,
+    statement: &ApprovalStatement<'_>,
+    prior_signatures: &[String],
+) -> Result<Vec<u8>, serde_json::Error> {
+    let content_hash = content_hash.to_ascii_lowercase();
+    let payload = ApprovalPayload {
+        approved_at: statement.approved_at,
+        approved_by: statement.approved_by,
+        content_hash: &content_hash,
+        domain: APPROVAL_DOMAIN,
+        party: statement.party,
+        prior_signatures,
+        role: statement.role,
+    };
+    Ok(canonical::to_canonical_string(&payload)?.into_bytes())
+}
+
+/// Label an approval for messages: its role, or its position.
+fn label(index: usize, approval: &Approval) -> String {
+    match &approval.role {
+        Some(role) => format!("approvals[{}] ({})", index, role),
+        None => format!("approvals[{}]", index),
+    }
+}
+
+/// Check one sealed approval's signature against the chain so far, and that
+/// the keyring lets its signer approve for its party and role.
+fn verify_approval(
+    approval: &Approval,
+    content_hash: &str,
+    signature: &[u8],
+    prior_signatures: &[String],
+    keyring: &Keyring,
+) -> Result<(), String> {
+    let statement = ApprovalStatement {
+        party: approval.party.as_deref().ok_or("signature present but party is missing")?,
+        role: approval.role.as_deref().ok_or("signature present but role is missing")?,
+        approved_by: approval.approved_by.as_deref().ok_or("signature present but approved_by is missing")?,
+        approved_at: approval.approved_at.as_deref().ok_or("signature present but approved_at is missing")?,
+    };
+    let approved_at = DateTime::parse_from_rfc3339(statement.approved_at)
+        .map_err(|e| format!("invalid approved_at '{}': {}", statement.approved_at, e))?
+        .with_timezone(&Utc);
+
+    let message = chain_message(content_hash, &statement, prior_signatures).map_err(|e| e.to_string())?;
+    let sig = Signature::from_slice(signature).map_err(|e| format!("malformed signature: {}", e))?;
+    let _ = keyring
+        .verify(statement.approved_by, approved_at, &message, &sig)
+        .map_err(|e| e.to_string())?;
+    keyring
+        .authorize_approval(statement.approved_by, statement.party, statement.role)
+        .map_err(|e| e.to_string())
+}
+
+/// Evaluate approvals against the requirements for a document with `content_hash`.
+///
+/// Signed approvals count towards the requirements only when they verify
+/// against the keyring; without a keyring, every signed approval is an
+/// error. `minimum_approvals` counts distinct parties.
+pub(crate) fn evaluate(
+    approvals: &[Approval],
+    requirements: Option<&ApprovalRequirements>,
+    content_hash: &str,
+    keyring: Option<&Keyring>,
+) -> ApprovalEvaluation {
+    let mut eval = ApprovalEvaluation::default();
+
+    let valid_hash = !content_hash.is_empty() && content_hash.chars().all(|c| c.is_ascii_hexdigit());
+    if !valid_hash {
+        eval.errors.push("content_hash is not valid hex; approval signatures cannot be checked".to_string());
+    }
+
+    let mut prior_signatures: Vec<String> = Vec::new();
+    let mut signed_roles: BTreeSet<&str> = BTreeSet::new();
+    let mut signed_parties: BTreeSet<&str> = BTreeSet::new();
+
+    for (i, approval) in approvals.iter().enumerate() {
+        let name = label(i, approval);
+        let status = approval.status.as_deref().unwrap_or_default();
+
+        if approval.role.is_none() {
+            eval.errors.push(format!("{}: missing role", name));
+        }
+        if approval.party.is_none() {
+            eval.errors.push(format!("{}: missing party", name));
+        }
+        let role_allowed = match (requirements, approval.role.as_deref()) {
+            (Some(req), Some(role)) if !req.allows_role(role) => {
+                eval.errors.push(format!("{}: role '{}' is neither a required nor an optional role", name, role));
+                false
+            }
+            _ => true,
+        };
+        if !VALID_APPROVAL_STATUSES.contains(&status) {
+            eval.errors.push(format!("{}: invalid status '{}'", name, status));
+            continue;
+        }
+        if matches!(status, "approved" | "sealed") && approval.approved_by.is_none() {
+            eval.errors.push(format!("{}: status is '{}' but approved_by is missing", name, status));
+        }
+
+        let signature = match (&approval.signature, status) {
+            (Some(sig), "sealed") => sig,
+            (None, "sealed") => {
+                eval.errors.push(format!("{}: status is 'sealed' but signature is missing", name));
+                continue;
+            }
+            (Some(_), _) => {
+                eval.errors.push(format!("{}: signature present but status is '{}'", name, status));
+                continue;
+            }
+            (None, _) => continue,
+        };
+
+        let sig_bytes = match hex::decode(signature) {
+            Ok(bytes) => bytes,
+            Err(_) => {
+                eval.errors.push(format!("{}: signature is not valid hex", name));
+                continue;
+            }
+        };
+
+        let valid = match keyring {
+            Some(kr) if valid_hash => match verify_approval(approval, content_hash, &sig_bytes, &prior_signatures, kr) {
+                Ok(()) => true,
+                Err(e) => {
+                    eval.errors.push(format!("{}: {}", name, e));
+                    false
+                }
+            },
+            Some(_) => false,
+            None => {
+                eval.errors.push(format!("{}: signature not verified: signer keyring could not be loaded", name));
+                false
+            }
+        };
+
+        if valid
+            && role_allowed
+            && let (Some(role), Some(party)) = (approval.role.as_deref(), approval.party.as_deref()) {
+            let _ = signed_roles.insert(role);
+            let _ = signed_parties.insert(party);
+        }
+        prior_signatures.push(hex::encode(&sig_bytes));
+    }
+
+    if let Some(req) = requirements {
+        let minimum = req.minimum_approvals.unwrap_or(0) as usize;
+        if signed_parties.len() < minimum {
+            eval.unmet.push(format!(
+                "minimum_approvals is {} but {} party(ies) have signed",
+                minimum,
+                signed_parties.len()
+            ));
+        }
+        for role in &req.required_roles {
+            if !signed_roles.contains(&role.as_str()) {
+                eval.unmet.push(format!("required role '{}' has no signed approval", role));
+            }
+        }
+    }
+
+    eval.signed_parties = signed_parties.into_iter().map(str::to_string).collect();
+    eval.signed_roles = signed_roles.into_iter().map(str::to_string).collect();
+    eval
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keyring::tests::{bob_key, test_key, test_keyring};
+    use ed25519_dalek::{Signer, SigningKey};
+
+    const CONTENT_HASH: &str = "ABCD";
+    const APPROVED_AT: &str = "2025-02-01T00:00:00Z";
+
+    /// A sealed approval signed by `key` on top of `prior` signatures.
+    fn sealed(party: &str, role: &str, signer: &str, key: &SigningKey, prior: &[&Approval]) -> Approval {
+        let statement = ApprovalStatement {
+            party,
+            role,
+            approved_by: signer,
+            approved_at: APPROVED_AT,
+        };
+        let prior: Vec<String> = prior.iter().filter_map(|a| a.signature.clone()).collect();
+        let message = chain_message(CONTENT_HASH, &statement, &prior).unwrap_or_default();
+        Approval {
+            party: Some(party.to_string()),
+            role: Some(role.to_string()),
+            status: Some("sealed".to_string()),
+            approved_by: Some(signer.to_string()),
+            approved_at: Some(APPROVED_AT.to_string()),
+            signature: Some(hex::encode(key.sign(&message).to_bytes())),
+        }
+    }
+
+    fn requirements(minimum: u32, required: &[&str], optional: &[&str]) -> ApprovalRequirements {
+        ApprovalRequirements {
+            minimum_approvals: Some(minimum),
+            required_roles: required.iter().map(|r| r.to_string()).collect(),
+            optional_roles: optional.iter().map(|r| r.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn chain_message_binds_party_role_signer_time_and_prior_signatures() {
+        let statement = ApprovalStatement {
+            party: "Xonaix",
+            role: "author",
+            approved_by: "alice",
+            approved_at: APPROVED_AT,
+        };
+        let message = chain_message(CONTENT_HASH, &statement, &["01".to_string()]).unwrap_or_default();
+        assert_eq!(
+            String::from_utf8(message).unwrap_or_default(),
+            r#"{"approved_at":"2025-02-01T00:00:00Z","approved_by":"alice","content_hash":"abcd","domain":"xonaix-approval-signature/v1","party":"Xonaix","prior_signatures":["01"],"role":"author"}"#
+        );
+    }
+
+    #[test]
+    fn valid_chain_meets_requirements() {
+        let author = sealed("Xonaix", "author", "alice", &test_key(), &[]);
+        let customer = sealed("Bank X", "customer", "bob", &bob_key(), &[&author]);
+        let req = requirements(2, &["author", "customer"], &["auditor"]);
+        let eval = evaluate(&[author, customer], Some(&req), CONTENT_HASH, Some(&test_keyring(None)));
+        assert!(eval.errors.is_empty(), "{:?}", eval.errors);
+        assert!(eval.unmet.is_empty(), "{:?}", eval.unmet);
+    }
+
+    /// Regression: approvals used to count without a keyring.
+    #[test]
+    fn fails_closed_without_keyring() {
+        let author = sealed("Xonaix", "author", "alice", &test_key(), &[]);
+        let eval = evaluate(&[author], Some(&requirements(1, &["author"], &[])), CONTENT_HASH, None);
+        assert_eq!(eval.errors.len(), 1);
+        assert_eq!(eval.unmet.len(), 2);
+    }
+
+    /// Regression: role and party were not signed, so they could be relabelled.
+    #[test]
+    fn relabelled_role_fails() {
+        let mut author = sealed("Xonaix", "author", "alice", &test_key(), &[]);
+        author.role = Some("customer".to_string());
+        let eval = evaluate(&[author], None, CONTENT_HASH, Some(&test_keyring(None)));
+        assert!(eval.errors.iter().any(|e| e.contains("does not verify")), "{:?}", eval.errors);
+    }
+
+    #[test]
+    fn signer_must_hold_the_role_and_party() {
+        let keyring = test_keyring(None);
+        let as_customer = sealed("Bank X", "customer", "alice", &test_key(), &[]);
+        let for_other_party = sealed("Bank X", "author", "alice", &test_key(), &[]);
+        for approval in [as_customer, for_other_party] {
+            let eval = evaluate(&[approval], None, CONTENT_HASH, Some(&keyring));
+            assert!(eval.errors.iter().any(|e| e.contains("not authorized to approve")), "{:?}", eval.errors);
+        }
+    }
+
+    #[test]
+    fn minimum_approvals_counts_distinct_parties() {
+        let first = sealed("Xonaix", "author", "alice", &test_key(), &[]);
+        let second = sealed("Xonaix", "author", "alice", &test_key(), &[&first]);
+        let eval = evaluate(&[first, second], Some(&requirements(2, &[], &["author"])), CONTENT_HASH, Some(&test_keyring(None)));
+        assert!(eval.errors.is_empty(), "{:?}", eval.errors);
+        assert_eq!(eval.unmet.len(), 1);
+    }
+
+    #[test]
+    fn undeclared_role_is_an_error() {
+        let author = sealed("Xonaix", "author", "alice", &test_key(), &[]);
+        let eval = evaluate(&[author], Some(&requirements(1, &["customer"], &[])), CONTENT_HASH, Some(&test_keyring(None)));
+        assert!(eval.errors.iter().any(|e| e.contains("neither a required nor an optional role")));
+        assert_eq!(eval.unmet.len(), 2);
+    }
+}
@@ -0,0 +1,931 @@
+//! Library BOM module.
+//!
+//! Generates the Library Bill of Materials defined in LIBRARY_SEALING_CONTRACT
+//! §5: the library version, every registered unit with its version, and the
+//! hash of each unit manifest. Seals a BOM with a detached Ed25519 signature
+//! and verifies a sealed release offline per §8 and §10.
+//!
+//! Manifest and BOM hashes are SHA3-512 over the canonical JSON form (RFC 8785)
+//! rather than the bytes on disk. The seal signs the BOM hash together with
+//! the library version, signer and signing time.
+
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, Signer};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+use crate::canonical;
+use crate::common::{find_repo_root, relative_path};
+use crate::keyring::{self, KeyMatch, KeyringError};
+use crate::manifest::{self, ManifestError, ManifestFormat};
+use crate::merkle::{self, InvalidHash};
+use crate::sign::{self, SignError};
+use crate::unit::{self, UnitError};
+use crate::version::{self, Version};
+
+/// Errors during BOM operations.
+#[derive(Debug, Error)]
+pub enum BomError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Regex error: {0}")]
+    Regex(#[from] regex::Error),
+
+    #[error(transparent)]
+    Manifest(#[from] ManifestError),
+
+    #[error(transparent)]
+    Unit(#[from] UnitError),
+
+    #[error(transparent)]
+    Keyring(#[from] KeyringError),
+
+    #[error(transparent)]
+    InvalidHash(#[from] InvalidHash),
+
+    #[error(transparent)]
+    Sign(#[from] SignError),
+
+    #[error("Cannot find specs/ directory")]
+    NoSpecsDir,
+
+    #[error("Invalid library version: {0} (expected LIB-MAJOR.MINOR.PATCH)")]
+    InvalidVersion(String),
+
+    #[error("BOM not found: {0}")]
+    BomNotFound(PathBuf),
+
+    #[error("{0} is already sealed, library versions are immutable")]
+    AlreadySealed(String),
+
+    #[error("{0} is already sealed; the initial release exception (§6.1) applies once only, use `bom stage`")]
+    InitialReleaseSealed(String),
+
+    #[error("No sealed release found; use `bom generate` for the initial release")]
+    NoSealedRelease,
+
+    #[error("{0} has no sealed BOM")]
+    NotSealed(String),
+
+    #[error("{0} must be greater than the previous release {1}")]
+    VersionNotIncreased(String, String),
+
+    #[error("BOM staging refused: {0} violation(s)")]
+    StagingRefused(usize),
+
+    #[error("BOM verification failed: {0} rule(s) failed")]
+    VerificationFailed(u32),
+}
+
+/// BOM format version.
+const BOM_VERSION: &str = "1.0.0";
+
+/// Hash algorithm for unit manifest hashes.
+pub(crate) const BOM_HASH_ALG: &str = "SHA3-512";
+
+/// Domain separator of BOM seal signatures.
+const SEAL_DOMAIN: &str = "xonaix-bom-seal/v1";
+
+/// Library Bill of Materials.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct LibraryBom {
+    pub bom_version: String,
+    pub generator: String,
+    pub hash_alg: String,
+    pub library_version: String,
+    /// Merkle root over the unit manifest roots.
+    pub merkle_root: String,
+    pub unit_count: usize,
+    pub units: Vec<BomUnit>,
+}
+
+/// A unit as recorded in the BOM.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct BomUnit {
+    pub manifest: String,
+    pub manifest_hash: String,
+    /// Merkle root over the unit manifest's file hashes.
+    pub merkle_root: String,
+    /// Library release in which this unit version was sealed.
+    pub sealed_in: String,
+    pub unit_id: String,
+    pub version: String,
+}
+
+/// Detached seal over a BOM file.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct BomSeal {
+    pub bom_hash: String,
+    pub hash_alg: String,
+    pub library_version: String,
+    pub signature: String,
+    pub signed_at: String,
+    pub signed_by: String,
+}
+
+/// Statement signed by a BOM seal.
+#[derive(Debug, Serialize)]
+struct SealPayload<'a> {
+    bom_hash: &'a str,
+    domain: &'a str,
+    library_version: &'a str,
+    signed_at: &'a str,
+    signed_by: &'a str,
+}
+
+/// Bytes signed for a seal: the canonical JSON of the BOM hash, library
+/// version, signer and signing time, under the BOM seal domain.
+pub(crate) fn seal_message(seal: &BomSeal) -> Result<Vec<u8>, serde_json::Error> {
+    let bom_hash = seal.bom_hash.to_ascii_lowercase();
+    let payload = SealPayload {
+        bom_hash: &bom_hash,
+        domain: SEAL_DOMAIN,
+        library_version: &seal.library_version,
+        signed_at: &seal.signed_at,
+        signed_by: &seal.signed_by,
+    };
+    Ok(canonical::to_canonical_string(&payload)?.into_bytes())
+}
+
+/// Check that a library version has the form `LIB-X.Y.Z`.
+pub(crate) fn validate_library_version(version: &str) -> Result<(), BomError> {
+    let re = Regex::new(r"^LIB-(0|[1-9]\d*)\.(0|[1-9]\d*)\.(0|[1-9]\d*)$")?;
+    if re.is_match(version) {
+        Ok(())
+    } else {
+        Err(BomError::InvalidVersion(version.to_string()))
+    }
+}
+
+/// Path of the BOM file for a library version.
+pub(crate) fn bom_path(repo_root: &Path, library_version: &str) -> PathBuf {
+    manifest::manifests_dir(repo_root).join(format!("BOM_{}.json", library_version))
+}
+
+/// Path of the detached seal for a library version.
+pub(crate) fn seal_path(repo_root: &Path, library_version: &str) -> PathBuf {
+    manifest::manifests_dir(repo_root).join(format!("BOM_{}.seal.json", library_version))
+}
+
+/// Load a BOM from disk.
+pub(crate) fn load_bom(path: &Path) -> Result<LibraryBom, BomError> {
+    if !path.exists() {
+        return Err(BomError::BomNotFound(path.to_path_buf()));
+    }
+    Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+}
+
+/// Compute the SHA3-512 hash of a JSON file over its canonical form.
+pub(crate) fn hash_file(path: &Path) -> Result<String, BomError> {
+    Ok(canonical::canonical_hash(&fs::read_to_string(path)?)?)
+}
+
+/// Parse `LIB-X.Y.Z` for ordering.
+fn parse_library_version(library_version: &str) -> Option<Version> {
+    version::parse(library_version).ok().filter(|v| v.prefix == "LIB")
+}
+
+/// Order a unit's current version against the version sealed previously.
+///
+/// Both must parse as `{PREFIX}-MAJOR.MINOR.PATCH` with the same prefix.
+fn compare_unit_versions(previous: &str, current: &str) -> Result<Ordering, String> {
+    let prev = version::parse(previous).map_err(|e| format!("sealed version: {}", e))?;
+    let cur = version::parse(current).map_err(|e| format!("version: {}", e))?;
+    prev.partial_cmp(&cur)
+        .map(Ordering::reverse)
+        .ok_or_else(|| format!("version prefix changed from {} to {}", prev.prefix, cur.prefix))
+}
+
+/// Highest library version that has a sealed BOM, if any.
+fn latest_sealed_version(repo_root: &Path) -> Result<Option<String>, BomError> {
+    let dir = manifest::manifests_dir(repo_root);
+    if !dir.exists() {
+        return Ok(None);
+    }
+
+    let mut latest: Option<(Version, String)> = None;
+    for entry in fs::read_dir(dir)? {
+        let name = entry?.file_name().to_string_lossy().to_string();
+        let version = match name.strip_prefix("BOM_").and_then(|n| n.strip_suffix(".seal.json")) {
+            Some(v) => v.to_string(),
+            None => continue,
+        };
+        if let Some(key) = parse_library_version(&version)
+            && latest.as_ref().is_none_or(|(k, _)| key > *k)
+        {
+            latest = Some((key, version));
+        }
+    }
+
+    Ok(latest.map(|(_, v)| v))
+}
+
+/// A registered unit with its refreshed manifest, not yet written to disk.
+struct PendingUnit {
+    unit_id: String,
+    version: String,
+    manifest_path: PathBuf,
+    manifest_content: String,
+    manifest_hash: String,
+    manifest_changed: bool,
+    merkle_root: String,
+}
+
+/// Compute the current manifest of every registered unit, sorted by unit_id.
+fn collect_units(repo_root: &Path) -> Result<Vec<PendingUnit>, BomError> {
+    let registry = unit::load_registry(repo_root)?;
+    let pinned = manifest::pinned_timestamp(None)?;
+    let generated_at = pinned.clone().unwrap_or_else(manifest::now_timestamp);
+
+    let mut unit_ids: Vec<&String> = registry.units.keys().collect();
+    unit_ids.sort();
+
+    let mut units = Vec::new();
+    for unit_id in unit_ids {
+        let entry = &registry.units[unit_id];
+        let unit_path = repo_root.join(&entry.path);
+        let unit_json = unit::load_unit_json(&unit_path.join("UNIT.json"))?;
+
+        let (manifest_path, format, existing) = manifest::resolve_manifest(
+            &manifest::manifests_dir(repo_root),
+            &manifest::unit_manifest_stem(unit_id),
+            None,
+        );
+        let mut manifest = manifest::generate_unit_manifest(&unit_path, repo_root, &generated_at, format)?;
+        let (manifest_content, manifest_changed) = manifest::refreshed_content(
+            existing.as_deref().unwrap_or(&manifest_path),
+            &mut manifest,
+            pinned.is_some(),
+        )?;
+
+        units.push(PendingUnit {
+            unit_id: unit_id.clone(),
+            version: unit_json.version,
+            manifest_path,
+            manifest_hash: canonical::canonical_hash(&manifest_content)?,
+            manifest_content,
+            manifest_changed,
+            merkle_root: manifest.computed_merkle_root()?,
+        });
+    }
+
+    Ok(units)
+}
+
+/// Write the unit manifests whose content changed.
+fn write_manifests(repo_root: &Path, units: &[PendingUnit]) -> Result<(), BomError> {
+    for u in units.iter().filter(|u| u.manifest_changed) {
+        if let Some(parent) = u.manifest_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&u.manifest_path, &u.manifest_content)?;
+        println!("  Updated manifest: {}", relative_path(&u.manifest_path, repo_root));
+    }
+    Ok(())
+}
+
+/// Assemble a BOM from pending units and the release each was sealed in.
+fn assemble_bom(
+    repo_root: &Path,
+    library_version: &str,
+    units: &[PendingUnit],
+    sealed_in: &[String],
+) -> Result<LibraryBom, BomError> {
+    let units: Vec<BomUnit> = units
+        .iter()
+        .zip(sealed_in)
+        .map(|(u, sealed_in)| BomUnit {
+            manifest: relative_path(&u.manifest_path, repo_root),
+            manifest_hash: u.manifest_hash.clone(),
+            merkle_root: u.merkle_root.clone(),
+            sealed_in: sealed_in.clone(),
+            unit_id: u.unit_id.clone(),
+            version: u.version.clone(),
+        })
+        .collect();
+
+    let roots: Vec<&str> = units.iter().map(|u| u.merkle_root.as_str()).collect();
+
+    Ok(LibraryBom {
+        bom_version: BOM_VERSION.to_string(),
+        generator: "tools/xonaix-library-tools".to_string(),
+        hash_alg: BOM_HASH_ALG.to_string(),
+        library_version: library_version.to_string(),
+        merkle_root: merkle::root_of_roots(&roots)?,
+        unit_count: units.len(),
+        units,
+    })
+}
+
+/// Write a BOM to its output path.
+fn write_bom(repo_root: &Path, bom: &LibraryBom, output: Option<String>) -> Result<PathBuf, BomError> {
+    let output_path = match output {
+        Some(path) => PathBuf::from(path),
+        None => bom_path(repo_root, &bom.library_version),
+    };
+    if let Some(parent) = output_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&output_path, render_bom(bom)?)?;
+    Ok(output_path)
+}
+
+/// Render a BOM in its committed form.
+pub(crate) fn render_bom(bom: &LibraryBom) -> Result<String, BomError> {
+    Ok(format!("{}\n", serde_json::to_string_pretty(bom)?))
+}
+
+/// Run the `bom generate` command.
+///
+/// Produces the initial BOM in which every unit is sealed. Per
+/// LIBRARY_SEALING_CONTRACT §6.1 this is permitted once only; later
+/// releases are built with `bom stage`.
+pub fn generate(
+    repo_root_arg: Option<String>,
+    library_version: String,
+    output: Option<String>,
+) -> Result<(), BomError> {
+    let repo_root = match repo_root_arg {
+        Some(path) => PathBuf::from(path),
+        None => find_repo_root()?.ok_or(BomError::NoSpecsDir)?,
+    };
+
+    validate_library_version(&library_version)?;
+
+    if let Some(previous) = latest_sealed_version(&repo_root)? {
+        return Err(BomError::InitialReleaseSealed(previous));
+    }
+
+    println!("=== XONAIX LIBRARY BOM GENERATION ===");
+    println!("Repository: {}", repo_root.display());
+    println!("Library Version: {}", library_version);
+    println!();
+
+    let units = collect_units(&repo_root)?;
+    write_manifests(&repo_root, &units)?;
+
+    let sealed_in = vec![library_version.clone(); units.len()];
+    let bom = assemble_bom(&repo_root, &library_version, &units, &sealed_in)?;
+    let output_path = write_bom(&repo_root, &bom, output)?;
+
+    for u in &bom.units {
+        println!("  {:40} {:12} {}...", u.unit_id, u.version, &u.manifest_hash[..16]);
+    }
+
+    println!();
+    println!("Generated: {}", output_path.display());
+    println!("Units: {}", bom.unit_count);
+
+    Ok(())
+}
+
+/// Run the `bom stage` command.
+///
+/// Diffs the current unit manifests against the previous sealed BOM and
+/// builds the next BOM per LIBRARY_SEALING_CONTRACT §6.2 and §7.2: new and
+/// version-changed units are sealed in this release, unchanged units keep
+/// referencing the release that sealed them.
+pub fn stage(
+    repo_root_arg: Option<String>,
+    library_version: String,
+    previous: Option<String>,
+    output: Option<String>,
+) -> Result<(), BomError> {
+    let repo_root = match repo_root_arg {
+        Some(path) => PathBuf::from(path),
+        None => find_repo_root()?.ok_or(BomError::NoSpecsDir)?,
+    };
+
+    validate_library_version(&library_version)?;
+
+    let previous = match previous {
+        Some(v) => {
+            validate_library_version(&v)?;
+            if !seal_path(&repo_root, &v).exists() {
+                return Err(BomError::NotSealed(v));
+            }
+            v
+        }
+        None => latest_sealed_version(&repo_root)?.ok_or(BomError::NoSealedRelease)?,
+    };
+    if parse_library_version(&library_version) <= parse_library_version(&previous) {
+        return Err(BomError::VersionNotIncreased(library_version, previous));
+    }
+    if seal_path(&repo_root, &library_version).exists() {
+        return Err(BomError::AlreadySealed(library_version));
+    }
+
+    let previous_bom = load_bom(&bom_path(&repo_root, &previous))?;
+    let previous_units: BTreeMap<&str, &BomUnit> =
+        previous_bom.units.iter().map(|u| (u.unit_id.as_str(), u)).collect();
+
+    println!("=== XONAIX LIBRARY BOM STAGING ===");
+    println!("Repository: {}", repo_root.display());
+    println!("Library Version: {}", library_version);
+    println!("Previous Release: {}", previous);
+    println!();
+
+    let units = collect_units(&repo_root)?;
+
+    let mut sealed_in = Vec::new();
+    let mut violations = Vec::new();
+    let mut staged = 0usize;
+    for u in &units {
+        let prev = match previous_units.get(u.unit_id.as_str()) {
+            Some(prev) => prev,
+            None => {
+                println!("  NEW:       {:40} {}", u.unit_id, u.version);
+                sealed_in.push(library_version.clone());
+                staged += 1;
+                continue;
+            }
+        };
+
+        match compare_unit_versions(&prev.version, &u.version) {
+            Err(reason) => {
+                violations.push(format!("{}: {}", u.unit_id, reason));
+                sealed_in.push(prev.sealed_in.clone());
+            }
+            Ok(Ordering::Less) => {
+                violations.push(format!(
+                    "{}: version {} is lower than {} sealed in {}",
+                    u.unit_id, u.version, prev.version, prev.sealed_in
+                ));
+                sealed_in.push(prev.sealed_in.clone());
+            }
+            Ok(Ordering::Greater) => {
+                println!("  CHANGED:   {:40} {} -> {}", u.unit_id, prev.version, u.version);
+                sealed_in.push(library_version.clone());
+                staged += 1;
+            }
+            Ok(Ordering::Equal) if !prev.manifest_hash.eq_ignore_ascii_case(&u.manifest_hash) => {
+                violations.push(format!(
+                    "{}: content changed without a version bump (still {})",
+                    u.unit_id, u.version
+                ));
+                sealed_in.push(prev.sealed_in.clone());
+            }
+            Ok(Ordering::Equal) => {
+                println!("  UNCHANGED: {:40} {} (sealed in {})", u.unit_id, u.version, prev.sealed_in);
+                sealed_in.push(prev.sealed_in.clone());
+            }
+        }
+    }
+
+    let current: BTreeSet<&str> = units.iter().map(|u| u.unit_id.as_str()).collect();
+    for unit_id in previous_units.keys().filter(|id| !current.contains(*id)) {
+        violations.push(format!("{}: unit in {} is no longer registered", unit_id, previous));
+    }
+
+    if staged == 0 && violations.is_empty() {
+        violations.push("no new or version-changed units; the release would only reseal unchanged units".to_string());
+    }
+
+    println!();
+    if !violations.is_empty() {
+        for v in &violations {
+            println!("FAIL: {}", v);
+        }
+        println!();
+        println!("=== BOM STAGING REFUSED ===");
+        return Err(BomError::StagingRefused(violations.len()));
+    }
+
+    write_manifests(&repo_root, &units)?;
+    let bom = assemble_bom(&repo_root, &library_version, &units, &sealed_in)?;
+    let output_path = write_bom(&repo_root, &bom, output)?;
+
+    println!("=== BOM STAGING COMPLETE ===");
+    println!("Generated: {}", output_path.display());
+    println!("Staged: {}", staged);
+    println!("Referenced: {}", bom.unit_count - staged);
+
+    Ok(())
+}
+
+/// Run the `bom seal` command.
+///
+/// Refuses to seal unless the keyring lists `signer` as a release signer
+/// holding this key, valid and not revoked now.
+pub fn seal(
+    repo_root_arg: Option<String>,
+    library_version: String,
+    key_path: String,
+    signer: String,
+    keyring_path: Option<String>,
+) -> Result<(), BomError> {
+    let repo_root = match repo_root_arg {
+        Some(path) => PathBuf::from(path),
+        None => find_repo_root()?.ok_or(BomError::NoSpecsDir)?,
+    };
+
+    validate_library_version(&library_version)?;
+
+    let bom_file = bom_path(&repo_root, &library_version);
+    let bom = load_bom(&bom_file)?;
+    let seal_file = seal_path(&repo_root, &library_version);
+    if seal_file.exists() {
+        return Err(BomError::AlreadySealed(library_version));
+    }
+
+    let key = sign::load_signing_key(Path::new(&key_path))?;
+    let keyring = keyring::load_keyring(&repo_root, keyring_path.as_deref().map(Path::new))?;
+    let now = Utc::now();
+    let key_id = keyring.signing_key(&signer, &key.verifying_key(), now)?.key_id.clone();
+    keyring.authorize_release(&signer)?;
+
+    let mut seal = BomSeal {
+        bom_hash: hash_file(&bom_file)?,
+        hash_alg: BOM_HASH_ALG.to_string(),
+        library_version: bom.library_version,
+        signature: String::new(),
+        signed_at: now.format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+        signed_by: signer,
+    };
+    seal.signature = hex::encode(key.sign(&seal_message(&seal)?).to_bytes());
+    fs::write(&seal_file, format!("{}\n", serde_json::to_string_pretty(&seal)?))?;
+
+    println!("=== XONAIX LIBRARY BOM SEAL ===");
+    println!("Library Version: {}", library_version);
+    println!("BOM Hash: {}...", &seal.bom_hash[..16]);
+    println!("Signer: {}", seal.signed_by);
+    println!("Key ID: {}", key_id);
+    println!("Public Key: {}", hex::encode(key.verifying_key().to_bytes()));
+    println!("Sealed: {}", seal_file.display());
+
+    Ok(())
+}
+
+/// Print the outcome of one verification rule.
+fn report_rule(failures: &[String], pass_message: &str, failed_rules: &mut u32) {
+    if failures.is_empty() {
+        println!("PASS: {}", pass_message);
+    } else {
+        for f in failures {
+            println!("  FAIL: {}", f);
+        }
+        *failed_rules += 1;
+    }
+}
+
+/// Check the BOM file hash and signature against its detached seal.
+pub(crate) fn check_seal(bom_file: &Path, seal_file: &Path, bom: &LibraryBom) -> Result<BomSeal, String> {
+    if !seal_file.exists() {
+        return Err(format!("Seal not found: {}", seal_file.display()));
+    }
+    let seal: BomSeal = fs::read_to_string(seal_file)
+        .map_err(|e| e.to_string())
+        .and_then(|c| serde_json::from_str(&c).map_err(|e| e.to_string()))?;
+
+    if seal.hash_alg != BOM_HASH_ALG {
+        return Err(format!("Unsupported seal hash_alg: {}", seal.hash_alg));
+    }
+    if seal.library_version != bom.library_version {
+        return Err(format!(
+            "Seal is for {}, BOM is {}",
+            seal.library_version, bom.library_version
+        ));
+    }
+
+    let computed = hash_file(bom_file).map_err(|e| e.to_string())?;
+    if !computed.eq_ignore_ascii_case(&seal.bom_hash) {
+        return Err("BOM hash does not match the sealed bom_hash".to_string());
+    }
+
+    Ok(seal)
+}
+
+/// Check the seal signature and that the signer may seal releases.
+///
+/// The key is judged at the seal's `signed_at`. Returns the key that made the
+/// seal, including when it was revoked after sealing.
+pub(crate) fn check_signer(repo_root: &Path, keyring_path: Option<&Path>, seal: &BomSeal) -> Result<KeyMatch, String> {
+    let keyring = keyring::load_keyring(repo_root, keyring_path).map_err(|e| e.to_string())?;
+
+    let signed_at = DateTime::parse_from_rfc3339(&seal.signed_at)
+        .map_err(|e| format!("invalid signed_at '{}': {}", seal.signed_at, e))?
+        .with_timezone(&Utc);
+    let sig_bytes = hex::decode(&seal.signature).map_err(|_| "signature is not valid hex".to_string())?;
+    let sig = Signature::from_slice(&sig_bytes).map_err(|e| format!("malformed signature: {}", e))?;
+    let message = seal_message(seal).map_err(|e| e.to_string())?;
+
+    let key = keyring
+        .verify(&seal.signed_by, signed_at, &message, &sig)
+        .map_err(|e| e.to_string())?;
+    keyring.authorize_release(&seal.signed_by).map_err(|e| e.to_string())?;
+
+    Ok(key)
+}
+
+/// Compare a unit manifest with the files currently on disk.
+pub(crate) fn diff_unit_files(repo_root: &Path, unit_id: &str, manifest_file: &Path) -> Result<Vec<String>, BomError> {
+    let registry = unit::load_registry(repo_root)?;
+    let mut problems = Vec::new();
+
+    let entry = match registry.units.get(unit_id) {
+        Some(e) => e,
+        None => return Ok(problems),
+    };
+
+    let recorded = manifest::load_manifest(manifest_file)?;
+    let format = ManifestFormat::of_manifest(&recorded)?;
+    let recorded: BTreeMap<String, String> = recorded.files.into_iter().map(|f| (f.path, f.hash)).collect();
+    let current: BTreeMap<String, String> =
+        manifest::generate_unit_manifest(&repo_root.join(&entry.path), repo_root, &manifest::now_timestamp(), format)?
+            .files
+            .into_iter()
+            .map(|f| (f.path, f.hash))
+            .collect();
+
+    for (path, hash) in &recorded {
+        match current.get(path) {
+            None => problems.push(format!("{}: missing file {}", unit_id, path)),
+            Some(h) if h != hash => problems.push(format!("{}: file hash mismatch {}", unit_id, path)),
+            Some(_) => {}
+        }
+    }
+    for path in current.keys() {
+        if !recorded.contains_key(path) {
+            problems.push(format!("{}: extra file {}", unit_id, path));
+        }
+    }
+
+    Ok(problems)
+}
+
+/// Run the `bom verify` command.
+///
+/// Applies every verification rule of LIBRARY_SEALING_CONTRACT §8 using only
+/// the checked-out tree. No network access is required.
+pub fn verify(
+    repo_root_arg: Option<String>,
+    library_version: String,
+    keyring_path: Option<String>,
+) -> Result<(), BomError> {
+    let repo_root = match repo_root_arg {
+        Some(path) => PathBuf::from(path),
+        None => find_repo_root()?.ok_or(BomError::NoSpecsDir)?,
+    };
+
+    validate_library_version(&library_version)?;
+
+    let bom_file = bom_path(&repo_root, &library_version);
+    let bom = load_bom(&bom_file)?;
+
+    println!("=== XONAIX LIBRARY BOM VERIFICATION ===");
+    println!("Repository: {}", repo_root.display());
+    println!("Library Version: {}", library_version);
+    println!("BOM: {}", bom_file.display());
+    println!();
+
+    let mut failed_rules = 0u32;
+
+    // Rule 1: BOM hash matches the sealed artifact
+    println!("[1/5] Checking BOM hash against seal...");
+    let seal = match check_seal(&bom_file, &seal_path(&repo_root, &library_version), &bom) {
+        Ok(seal) => {
+            println!("PASS: BOM hash matches sealed artifact");
+            Some(seal)
+        }
+        Err(reason) => {
+            println!("FAIL: {}", reason);
+            failed_rules += 1;
+            None
+        }
+    };
+
+    // Rule 2: All referenced unit manifests exist
+    println!();
+    println!("[2/5] Checking referenced unit manifests exist...");
+    let mut missing = Vec::new();
+    let mut manifest_paths = Vec::new();
+    for u in &bom.units {
+        match manifest::repo_path(&repo_root, &u.manifest) {
+            Ok(path) if path.exists() => manifest_paths.push((u, path)),
+            Ok(_) => missing.push(format!("{}: manifest not found: {}", u.unit_id, u.manifest)),
+            Err(reason) => missing.push(format!("{}: manifest {}", u.unit_id, reason)),
+        }
+    }
+    report_rule(&missing, &format!("All {} unit manifests exist", bom.units.len()), &mut failed_rules);
+
+    // Rule 3: All unit manifest hashes match file contents
+    println!();
+    println!("[3/5] Checking unit manifest hashes...");
+    let mut mismatched = Vec::new();
+    for (u, path) in &manifest_paths {
+        match hash_file(path) {
+            Ok(hash) if hash.eq_ignore_ascii_case(&u.manifest_hash) => {}
+            Ok(_) => {
+                mismatched.push(format!("{}: manifest hash mismatch: {}", u.unit_id, u.manifest));
+                continue;
+            }
+            Err(e) => {
+                mismatched.push(format!("{}: cannot hash {}: {}", u.unit_id, u.manifest, e));
+                continue;
+            }
+        }
+        match manifest::load_manifest(path).and_then(|m| m.computed_merkle_root()) {
+            Ok(root) if root.eq_ignore_ascii_case(&u.merkle_root) => {}
+            Ok(_) => mismatched.push(format!("{}: merkle_root mismatch: {}", u.unit_id, u.manifest)),
+            Err(e) => mismatched.push(format!("{}: cannot read {}: {}", u.unit_id, u.manifest, e)),
+        }
+    }
+    let roots: Vec<&str> = bom.units.iter().map(|u| u.merkle_root.as_str()).collect();
+    match merkle::root_of_roots(&roots) {
+        Ok(root) if root.eq_ignore_ascii_case(&bom.merkle_root) => {}
+        Ok(_) => mismatched.push("BOM merkle_root does not match the unit roots".to_string()),
+        Err(e) => mismatched.push(format!("BOM merkle_root cannot be computed: {}", e)),
+    }
+    report_rule(&mismatched, "All unit manifest hashes and Merkle roots match", &mut failed_rules);
+
+    // Rule 4: No extra or missing files
+    println!();
+    println!("[4/5] Checking for extra or missing files...");
+    let mut drift = Vec::new();
+    match unit::load_registry(&repo_root) {
+        Ok(registry) => {
+            let bom_units: BTreeSet<&str> = bom.units.iter().map(|u| u.unit_id.as_str()).collect();
+            for unit_id in registry.units.keys() {
+                if !bom_units.contains(unit_id.as_str()) {
+                    drift.push(format!("{}: registered unit missing from BOM", unit_id));
+                }
+            }
+            for u in &bom.units {
+                if !registry.units.contains_key(&u.unit_id) {
+                    drift.push(format!("{}: BOM unit not in registry", u.unit_id));
+                }
+            }
+            for (u, path) in manifest_paths.iter().filter(|(u, _)| registry.units.contains_key(&u.unit_id)) {
+                match diff_unit_files(&repo_root, &u.unit_id, path) {
+                    Ok(problems) => drift.extend(problems),
+                    Err(e) => drift.push(format!("{}: cannot compare files: {}", u.unit_id, e)),
+                }
+            }
+        }
+        Err(e) => drift.push(format!("cannot load unit registry: {}", e)),
+    }
+    let referenced: BTreeSet<&str> = bom.units.iter().map(|u| u.manifest.as_str()).collect();
+    match fs::read_dir(manifest::manifests_dir(&repo_root)) {
+        Ok(entries) => {
+            for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+                let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                if name.starts_with("UNIT_MANIFEST_") {
+                    let rel = relative_path(&path, &repo_root);
+                    if !referenced.contains(rel.as_str()) {
+                        drift.push(format!("extra unit manifest not in BOM: {}", rel));
+                    }
+                }
+            }
+        }
+        Err(e) => drift.push(format!("cannot list manifests: {}", e)),
+    }
+    report_rule(&drift, "No extra or missing files", &mut failed_rules);
+
+    // Rule 5: The signer is authorized
+    println!();
+    println!("[5/5] Checking signer authorization...");
+    match &seal {
+        Some(seal) => match check_signer(&repo_root, keyring_path.as_deref().map(Path::new), seal) {
+            Ok(key) => {
+                println!("PASS: Signed by authorized release signer {} (key {})", seal.signed_by, key.key_id);
+                if let Some(revoked_at) = key.revoked_at {
+                    println!("WARN: key {} revoked at {} after sealing (re-seal required)", key.key_id, revoked_at.to_rfc3339());
+                }
+            }
+            Err(reason) => {
+                println!("FAIL: {}", reason);
+                failed_rules += 1;
+            }
+        },
+        None => {
+            println!("FAIL: No valid seal to check");
+            failed_rules += 1;
+        }
+    }
+
+    println!();
+    println!("=== BOM VERIFICATION COMPLETE ===");
+    if failed_rules == 0 {
+        println!("Result: RELEASE {} VERIFIED", library_version);
+        Ok(())
+    } else {
+        println!("Result: FAILED - {} rule(s) failed", failed_rules);
+        Err(BomError::VerificationFailed(failed_rules))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keyring::tests::test_key;
+
+    /// Write a keyring listing `test_key()` as release signer alice.
+    fn write_keyring(name: &str, revoked_at: Option<&str>) -> PathBuf {
+        let revocations = revoked_at
+            .map(|at| format!(r#"{{"key_id":"alice-2025","revoked_at":"{}","reason":"test"}}"#, at))
+            .unwrap_or_default();
+        let json = format!(
+            r#"{{"keyring_version":"{}","signers":{{"alice":{{"release_signer":true,"keys":[{{"key_id":"alice-2025","public_key":"{}","valid_from":"2025-01-01T00:00:00Z"}}]}}}},"revocations":[{}]}}"#,
+            keyring::KEYRING_VERSION,
+            hex::encode(test_key().verifying_key().to_bytes()),
+            revocations
+        );
+        let path = std::env::temp_dir().join(format!("xonaix-bom-keyring-{}-{}.json", name, std::process::id()));
+        let _ = fs::write(&path, json);
+        path
+    }
+
+    fn signed_seal(signed_at: &str) -> BomSeal {
+        let mut seal = BomSeal {
+            bom_hash: "AB".repeat(64),
+            hash_alg: BOM_HASH_ALG.to_string(),
+            library_version: "LIB-1.0.0".to_string(),
+            signature: String::new(),
+            signed_at: signed_at.to_string(),
+            signed_by: "alice".to_string(),
+        };
+        seal.signature = hex::encode(test_key().sign(&seal_message(&seal).unwrap_or_default()).to_bytes());
+        seal
+    }
+
+    fn check(name: &str, seal: &BomSeal, revoked_at: Option<&str>) -> Result<KeyMatch, String> {
+        let keyring = write_keyring(name, revoked_at);
+        let result = check_signer(Path::new("."), Some(&keyring), seal);
+        let _ = fs::remove_file(keyring);
+        result
+    }
+
+    #[test]
+    fn seal_message_binds_version_signer_and_time() {
+        let seal = BomSeal {
+            bom_hash: "AB".to_string(),
+            hash_alg: BOM_HASH_ALG.to_string(),
+            library_version: "LIB-1.0.0".to_string(),
+            signature: String::new(),
+            signed_at: "2025-02-01T00:00:00Z".to_string(),
+            signed_by: "alice".to_string(),
+        };
+        assert_eq!(
+            String::from_utf8(seal_message(&seal).unwrap_or_default()).unwrap_or_default(),
+            r#"{"bom_hash":"ab","domain":"xonaix-bom-seal/v1","library_version":"LIB-1.0.0","signed_at":"2025-02-01T00:00:00Z","signed_by":"alice"}"#
+        );
+    }
+
+    #[test]
+    fn accepts_valid_seal() {
+        assert!(check("valid", &signed_seal("2025-02-01T00:00:00Z"), None).is_ok_and(|k| k.key_id == "alice-2025" && k.revoked_at.is_none()));
+    }
+
+    /// Regression: rewriting signed_at after sealing must invalidate the seal.
+    #[test]
+    fn rejects_backdated_seal() {
+        let mut seal = signed_seal("2025-04-01T00:00:00Z");
+        seal.signed_at = "2025-02-01T00:00:00Z".to_string();
+        assert!(check("backdated", &seal, Some("2025-03-01T00:00:00Z")).is_err_and(|e| e.contains("does not verify")));
+    }
+
+    #[test]
+    fn reports_seal_from_key_revoked_later() {
+        let seal = signed_seal("2025-02-01T00:00:00Z");
+        assert!(check("revoked", &seal, Some("2025-03-01T00:00:00Z")).is_ok_and(|k| k.revoked_at.is_some()));
+    }
+
+    #[test]
+    fn rejects_seal_after_revocation() {
+        let seal = signed_seal("2025-04-01T00:00:00Z");
+        assert!(check("after-revocation", &seal, Some("2025-03-01T00:00:00Z")).is_err_and(|e| e.contains("revoked")));
+    }
+
+    #[test]
+    fn rejects_relabelled_seal() {
+        let mut seal = signed_seal("2025-02-01T00:00:00Z");
+        seal.library_version = "LIB-2.0.0".to_string();
+        assert!(check("relabelled", &seal, None).is_err());
+    }
+
+    #[test]
+    fn unit_versions_must_not_decrease_or_change_prefix() {
+        assert_eq!(compare_unit_versions("XLIB-1.2.0", "XLIB-1.10.0"), Ok(Ordering::Greater));
+        assert_eq!(compare_unit_versions("XLIB-1.2.0", "XLIB-1.2.0"), Ok(Ordering::Equal));
+        assert_eq!(compare_unit_versions("XLIB-1.2.0", "XLIB-1.1.9"), Ok(Ordering::Less));
+        assert!(compare_unit_versions("XLIB-1.2.0", "XGOV-2.0.0").is_err());
+        assert!(compare_unit_versions("XLIB-1.2.0", "1.3.0").is_err());
+    }
+
+    #[test]
+    fn library_version_format() {
+        assert!(validate_library_version("LIB-1.2.3").is_ok());
+        assert!(validate_library_version("LIB-01.2.3").is_err());
+        assert!(validate_library_version("XLIB-1.2.3").is_err());
+        assert!(parse_library_version("LIB-1.10.0") > parse_library_version("LIB-1.9.9"));
+    }
+}
@@ -0,0 +1,207 @@
+//! Canonical JSON module.
+//!
+//! Serializes JSON per the JSON Canonicalization Scheme (RFC 8785): object
+//! members sorted by UTF-16 code units, ECMAScript number formatting, minimal
+//! string escaping and no insignificant whitespace. Every hashed or signed
+//! JSON artifact is hashed over this form, so the hash does not depend on
+//! how the file on disk happens to be formatted.
+
+use serde::Serialize;
+use serde_json::Value;
+use sha3::{Digest, Sha3_512};
+
+/// Largest integer magnitude an IEEE-754 double represents exactly.
+const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+
+/// Serialize a value to canonical JSON.
+pub(crate) fn to_canonical_string<T: Serialize>(value: &T) -> Result<String, serde_json::Error> {
+    let value = serde_json::to_value(value)?;
+    let mut out = String::new();
+    write_value(&value, &mut out)?;
+    Ok(out)
+}
+
+/// Re-serialize a JSON document in canonical form.
+pub(crate) fn canonicalize_str(json: &str) -> Result<String, serde_json::Error> {
+    let value: Value = serde_json::from_str(json)?;
+    to_canonical_string(&value)
+}
+
+/// SHA3-512 over the canonical form of a JSON document, as lowercase hex.
+pub(crate) fn canonical_hash(json: &str) -> Result<String, serde_json::Error> {
+    let canonical = canonicalize_str(json)?;
+    let mut hasher = Sha3_512::new();
+    hasher.update(canonical.as_bytes());
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+fn write_value(value: &Value, out: &mut String) -> Result<(), serde_json::Error> {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::Number(n) => write_number(n, out)?,
+        Value::String(s) => write_string(s, out),
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_value(item, out)?;
+            }
+            out.push(']');
+        }
+        Value::Object(map) => {
+            let mut members: Vec<(&String, &Value)> = map.iter().collect();
+            members.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+
+            out.push('{');
+            for (i, (key, item)) in members.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_string(key, out);
+                out.push(':');
+                write_value(item, out)?;
+            }
+            out.push('}');
+        }
+    }
+    Ok(())
+}
+
+/// Write a string with the escaping mandated by RFC 8785 §3.2.2.2.
+fn write_string(s: &str, out: &mut String) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\u{08}' => out.push_str("\\b"),
+            '\t' => out.push_str("\\t"),
+            '\n' => out.push_str("\\n"),
+            '\u{0C}' => out.push_str("\\f"),
+            '\r' => out.push_str("\\r"),
+            c if c < '\u{20}' => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Write a number as ECMAScript `Number.prototype.toString` would (RFC 8785 §3.2.2.3).
+fn write_number(n: &serde_json::Number, out: &mut String) -> Result<(), serde_json::Error> {
+    if let Some(u) = n.as_u64() {
+        if u > MAX_SAFE_INTEGER {
+            return Err(serde::ser::Error::custom(format!("integer {} is not exactly representable", u)));
+        }
+        out.push_str(&u.to_string());
+        return Ok(());
+    }
+    if let Some(i) = n.as_i64() {
+        if i.unsigned_abs() > MAX_SAFE_INTEGER {
+            return Err(serde::ser::Error::custom(format!("integer {} is not exactly representable", i)));
+        }
+        out.push_str(&i.to_string());
+        return Ok(());
+    }
+
+    let f = n
+        .as_f64()
+        .ok_or_else(|| <serde_json::Error as serde::ser::Error>::custom("number is not finite"))?;
+    out.push_str(&format_f64(f));
+    Ok(())
+}
+
+/// Format a finite double using the ECMAScript shortest round-trip rules.
+fn format_f64(f: f64) -> String {
+    if f == 0.0 {
+        return "0".to_string();
+    }
+
+    let sign = if f < 0.0 { "-" } else { "" };
+
+    // `{:e}` yields the shortest round-trip digits, e.g. "1.25e-7".
+    let sci = format!("{:e}", f.abs());
+    let (mantissa, exponent) = sci.split_once('e').unwrap_or((sci.as_str(), "0"));
+    let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+    let k = digits.len() as i32;
+    let n = exponent.parse::<i32>().unwrap_or(0) + 1;
+
+    let body = if k <= n && n <= 21 {
+        format!("{}{}", digits, "0".repeat((n - k) as usize))
+    } else if 0 < n && n <= 21 {
+        format!("{}.{}", &digits[..n as usize], &digits[n as usize..])
+    } else if -6 < n && n <= 0 {
+        format!("0.{}{}", "0".repeat((-n) as usize), digits)
+    } else {
+        let exp = n - 1;
+        let exp_sign = if exp < 0 { "-" } else { "+" };
+        let frac = if k > 1 { format!(".{}", &digits[1..]) } else { String::new() };
+        format!("{}{}e{}{}", &digits[..1], frac, exp_sign, exp.abs())
+    };
+
+    format!("{}{}", sign, body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// RFC 8785 §3.2.2 sample input and its canonical form.
+    #[test]
+    fn rfc8785_sample() {
+        let input = r#"{
+            "numbers": [333333333.33333329, 1E30, 4.50, 2e-3, 0.000000000000000000000000001],
+            "string": "\u20ac$\u000F\u000aA'\u0042\u0022\u005c\\\"\/",
+            "literals": [null, true, false]
+        }"#;
+        assert_eq!(
+            canonicalize_str(input).unwrap_or_default(),
+            r#"{"literals":[null,true,false],"numbers":[333333333.3333333,1e+30,4.5,0.002,1e-27],"string":"€$\u000f\nA'B\"\\\\\"/"}"#
+        );
+    }
+
+    /// RFC 8785 §3.2.3: members sort by UTF-16 code units, not code points.
+    #[test]
+    fn rfc8785_sorting() {
+        let input = r#"{"\u20ac":"Euro Sign","\r":"Carriage Return","\ufb33":"Hebrew Letter Dalet With Dagesh","1":"One","\ud83d\ude00":"Emoji: Grinning Face","\u0080":"Control","\u00f6":"Latin Small Letter O With Diaeresis"}"#;
+        assert_eq!(
+            canonicalize_str(input).unwrap_or_default(),
+            "{\"\\r\":\"Carriage Return\",\"1\":\"One\",\"\u{80}\":\"Control\",\"ö\":\"Latin Small Letter O With Diaeresis\",\"€\":\"Euro Sign\",\"😀\":\"Emoji: Grinning Face\",\"\u{fb33}\":\"Hebrew Letter Dalet With Dagesh\"}"
+        );
+    }
+
+    /// ECMAScript number formatting, RFC 8785 Appendix B.
+    #[test]
+    fn rfc8785_numbers() {
+        let cases = [
+            ("-0.0", "0"),
+            ("1e21", "1e+21"),
+            ("1e20", "100000000000000000000"),
+            ("1e-7", "1e-7"),
+            ("0.000001", "0.000001"),
+            ("9007199254740991", "9007199254740991"),
+            ("-9007199254740991", "-9007199254740991"),
+            ("295147905179352830000", "295147905179352830000"),
+            ("1.7976931348623157e308", "1.7976931348623157e+308"),
+            ("5e-324", "5e-324"),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(canonicalize_str(input).unwrap_or_default(), expected, "{}", input);
+        }
+    }
+
+    #[test]
+    fn rejects_integers_beyond_double_precision() {
+        assert!(canonicalize_str("9007199254740993").is_err());
+        assert!(canonicalize_str("-9007199254740993").is_err());
+    }
+
+    #[test]
+    fn hash_ignores_formatting() {
+        let expected = "d4403f23b637b54131b2fe3efbd4e4095b9f8769384a0d52d6f8087efdcc32732a28ebad019dc936bb420a80b40534f38ad2d7a9226a2a5cb3952e9ee4e36318";
+        assert_eq!(canonical_hash(r#"{"a":1,"b":[true,null]}"#).unwrap_or_default(), expected);
+        assert_eq!(canonical_hash("{\n  \"b\": [ true, null ],\n  \"a\": 1\n}\n").unwrap_or_default(), expected);
+    }
+}
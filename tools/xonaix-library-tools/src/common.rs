@@ -0,0 +1,249 @@
+//! Shared helpers module.
+//!
+//! Repository discovery, document selection and YAML frontmatter access
+//! used by the individual commands.
+
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Find repository root by looking for specs/ directory.
+///
+/// Searches the current directory and its ancestors; `None` if no
+/// directory on the way up contains `specs/`.
+pub(crate) fn find_repo_root() -> std::io::Result<Option<PathBuf>> {
+    let current = std::env::current_dir()?;
+
+    Ok(current
+        .ancestors()
+        .find(|dir| dir.join("specs").exists())
+        .map(Path::to_path_buf))
+}
+
+/// Check if path should be excluded from document validation.
+pub(crate) fn should_exclude(path: &Path) -> bool {
+    let path_str = path.to_string_lossy();
+
+    // Exclude _deprecated/
+    if path_str.contains("_deprecated/") || path_str.contains("_deprecated\\") {
+        return true;
+    }
+
+    // Exclude _reference/
+    if path_str.contains("_reference/") || path_str.contains("_reference\\") {
+        return true;
+    }
+
+    // Exclude .git/
+    if path_str.contains(".git/") || path_str.contains(".git\\") {
+        return true;
+    }
+
+    // Exclude target/
+    if path_str.contains("/target/") || path_str.contains("\\target\\") {
+        return true;
+    }
+
+    // Exclude manifests/ (generated files)
+    if path_str.contains("manifests/") || path_str.contains("manifests\\") {
+        return true;
+    }
+
+    // Exclude _roadmap/
+    if path_str.contains("_roadmap/") || path_str.contains("_roadmap\\") {
+        return true;
+    }
+
+    false
+}
+
+/// Check if path should be excluded from the governance report.
+///
+/// Unlike [`should_exclude`], reference and roadmap documents are counted.
+pub(crate) fn should_exclude_from_report(path: &Path) -> bool {
+    let path_str = path.to_string_lossy();
+
+    path_str.contains("_deprecated")
+        || path_str.contains(".git")
+        || path_str.contains("target")
+        || path_str.contains("manifests")
+}
+
+/// Check if path should be excluded from a manifest.
+pub(crate) fn should_exclude_from_manifest(path: &Path, is_governance: bool) -> bool {
+    let path_str = path.to_string_lossy();
+
+    // Exclude .git artifacts
+    if path_str.contains(".git") {
+        return true;
+    }
+
+    // Exclude _reference/ directories
+    if path_str.contains("_reference/") || path_str.contains("_reference\\") {
+        return true;
+    }
+
+    // Exclude common non-content files
+    if let Some(name) = path.file_name() {
+        let name_str = name.to_string_lossy();
+        if name_str == ".gitignore" || name_str == ".gitattributes" || name_str == "CODEOWNERS" {
+            return true;
+        }
+    }
+
+    // For governance manifest: exclude the manifests/ subdirectory to avoid self-reference
+    if is_governance && (path_str.contains("manifests/") || path_str.contains("manifests\\")) {
+        return true;
+    }
+
+    false
+}
+
+/// Collect markdown documents in scope, or the single requested file.
+pub(crate) fn collect_documents(repo_root: &Path, file_path: Option<String>) -> std::io::Result<Vec<PathBuf>> {
+    if let Some(file) = file_path {
+        let path = PathBuf::from(&file);
+        if !path.exists() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("File not found: {}", file),
+            ));
+        }
+        return Ok(vec![path]);
+    }
+
+    Ok(WalkDir::new(repo_root.join("specs"))
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .map(|e| e.into_path())
+        .filter(|p| p.is_file() && !should_exclude(p))
+        .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("md"))
+        .collect())
+}
+
+/// Byte offset where the header ends and the body begins.
+///
+/// Points just past the closing `---` delimiter. Both delimiters must be a
+/// line of exactly `---`, so `----` and longer horizontal rules are not
+/// mistaken for the end of the header.
+pub(crate) fn body_offset(content: &str) -> Option<usize> {
+    let first_end = content.find('\n')?;
+    if content[..first_end].trim_end_matches('\r') != "---" {
+        return None;
+    }
+
+    let mut pos = first_end + 1;
+    for line in content[pos..].split_inclusive('\n') {
+        if line.trim_end_matches(['\n', '\r']) == "---" {
+            return Some(pos + 3);
+        }
+        pos += line.len();
+    }
+    None
+}
+
+/// Extract the YAML frontmatter from markdown content.
+pub(crate) fn extract_frontmatter(content: &str) -> Option<&str> {
+    let offset = body_offset(content)?;
+    Some(&content[3..offset - 4])
+}
+
+/// Get string value from optional YAML value.
+pub(crate) fn get_yaml_string(v: &Option<serde_yaml::Value>) -> Option<&str> {
+    match v {
+        Some(serde_yaml::Value::String(s)) => Some(s.as_str()),
+        _ => None,
+    }
+}
+
+/// Get a string field of a YAML mapping.
+pub(crate) fn get_yaml_field<'a>(value: &'a serde_yaml::Value, key: &str) -> Option<&'a str> {
+    value.get(key).and_then(|v| v.as_str())
+}
+
+/// Repository-relative path with forward slashes.
+pub(crate) fn relative_path(path: &Path, repo_root: &Path) -> String {
+    path.strip_prefix(repo_root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+/// Repository-relative path of a path given on the command line.
+///
+/// Resolves the path against the current directory and follows symlinks,
+/// so `./specs/x.md` and an absolute path name the same file.
+pub(crate) fn resolved_relative_path(path: &Path, repo_root: &Path) -> String {
+    let absolute = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir().map(|d| d.join(path)).unwrap_or_else(|_| path.to_path_buf())
+    };
+    let root = repo_root.canonicalize().unwrap_or_else(|_| repo_root.to_path_buf());
+    let absolute = absolute.canonicalize().unwrap_or(absolute);
+
+    relative_path(&absolute, &root)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn closing_delimiter_must_be_exactly_three_dashes() {
+        let content = "---\ntitle: x\n----\nnote: y\n---\nbody\n";
+        assert_eq!(extract_frontmatter(content), Some("\ntitle: x\n----\nnote: y"));
+        assert_eq!(&content[body_offset(content).unwrap_or_default()..], "\nbody\n");
+    }
+
+    #[test]
+    fn horizontal_rule_in_body_is_not_the_header_end() {
+        let content = "---\ntitle: x\n---\nabc\n\n-----\n\nmore\n";
+        assert_eq!(extract_frontmatter(content), Some("\ntitle: x"));
+    }
+
+    #[test]
+    fn opening_delimiter_must_be_exactly_three_dashes() {
+        assert_eq!(body_offset("----\ntitle: x\n---\nbody"), None);
+        assert_eq!(extract_frontmatter("---title: x\n---\nbody"), None);
+    }
+
+    #[test]
+    fn frontmatter_accepts_crlf_and_empty_headers() {
+        assert_eq!(extract_frontmatter("---\r\ntitle: x\r\n---\r\nbody"), Some("\r\ntitle: x\r"));
+        assert_eq!(extract_frontmatter("---\n---\nbody"), Some(""));
+        assert_eq!(extract_frontmatter("---\ntitle: x\n"), None);
+    }
+
+    #[test]
+    fn exclusions_cover_generated_and_reference_trees() {
+        assert!(should_exclude(Path::new("specs/_deprecated/OLD.md")));
+        assert!(should_exclude(Path::new("specs/_reference/constitution/X.md")));
+        assert!(should_exclude(Path::new("specs/_governance/manifests/M.md")));
+        assert!(should_exclude(Path::new("specs/_roadmap/PLAN.md")));
+        assert!(should_exclude(Path::new("tools/x/target/doc/README.md")));
+        assert!(should_exclude(Path::new("specs\\_reference\\X.md")));
+        assert!(!should_exclude(Path::new("specs/standards/sql/STANDARDS_SQL.md")));
+    }
+
+    #[test]
+    fn report_counts_reference_and_roadmap_documents() {
+        assert!(!should_exclude_from_report(Path::new("specs/_roadmap/PLAN.md")));
+        assert!(!should_exclude_from_report(Path::new("specs/_reference/X.md")));
+        assert!(should_exclude_from_report(Path::new("specs/_deprecated/OLD.md")));
+    }
+
+    #[test]
+    fn only_the_governance_manifest_skips_manifests() {
+        let path = Path::new("specs/_governance/manifests/MANIFEST_x.json");
+        assert!(should_exclude_from_manifest(path, true));
+        assert!(!should_exclude_from_manifest(path, false));
+        assert!(should_exclude_from_manifest(Path::new("specs/x/.gitignore"), false));
+    }
+
+    #[test]
+    fn relative_path_uses_forward_slashes() {
+        assert_eq!(relative_path(Path::new("/repo/specs/a/B.md"), Path::new("/repo")), "specs/a/B.md");
+        assert_eq!(relative_path(Path::new("elsewhere/B.md"), Path::new("/repo")), "elsewhere/B.md");
+    }
+}
@@ -0,0 +1,479 @@
+//! Constitutional conformance module.
+//!
+//! Verifies each document's `constitutional_conformance` block (header
+//! contract §6.9) against locally vendored copies of the Constitution and
+//! Zero Point, so conformance can be checked offline.
+//!
+//! Vendored documents live under `specs/_reference/constitution/`, indexed by
+//! `VENDORED.json`. Deviations a document lists must be recorded and approved
+//! in `specs/_governance/DEVIATION_REGISTER.json` against that document.
+//!
+//! A document whose conformance block is entirely null has not declared
+//! conformance; this is a warning, and a failure under `--strict`.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use walkdir::WalkDir;
+
+use crate::common::{extract_frontmatter, find_repo_root, get_yaml_string, relative_path, should_exclude};
+use crate::header;
+use crate::manifest::{self, HashAlg, ManifestError};
+
+/// Repository-relative directory of the vendored constitutional documents.
+const VENDORED_DIR: &str = "specs/_reference/constitution";
+
+/// Index of the vendored documents, inside [`VENDORED_DIR`].
+const VENDORED_INDEX: &str = "VENDORED.json";
+
+/// Supported vendored index version.
+const VENDORED_INDEX_VERSION: &str = "1.0.0";
+
+/// Repository-relative path of the deviation register.
+const DEVIATION_REGISTER_PATH: &str = "specs/_governance/DEVIATION_REGISTER.json";
+
+/// Supported deviation register version.
+const DEVIATION_REGISTER_VERSION: &str = "1.0.0";
+
+/// Days after which `last_verified` is stale.
+pub(crate) const DEFAULT_MAX_AGE_DAYS: i64 = 365;
+
+/// Errors during conformance verification.
+#[derive(Debug, Error)]
+pub enum ConformanceError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("JSON error in {0}: {1}")]
+    Json(PathBuf, serde_json::Error),
+
+    #[error("Unsupported version {1} in {0} (expected {2})")]
+    UnsupportedVersion(PathBuf, String, &'static str),
+
+    #[error(transparent)]
+    Manifest(#[from] ManifestError),
+
+    #[error("Cannot find specs/ directory")]
+    NoSpecsDir,
+
+    #[error("Conformance verification failed: {0} failure(s)")]
+    VerificationFailed(u32),
+}
+
+/// VENDORED.json schema
+#[derive(Debug, Deserialize)]
+struct VendoredIndex {
+    index_version: String,
+    documents: Vec<VendoredDocument>,
+}
+
+/// A vendored Constitution or Zero Point version.
+#[derive(Debug, Deserialize)]
+struct VendoredDocument {
+    /// `constitution` or `zero_point`.
+    kind: String,
+    version: String,
+    /// Path relative to the vendored directory.
+    path: String,
+    hash_alg: String,
+    hash: String,
+}
+
+/// DEVIATION_REGISTER.json schema
+#[derive(Debug, Deserialize)]
+struct DeviationRegister {
+    register_version: String,
+    deviations: BTreeMap<String, DeviationEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeviationEntry {
+    /// Repo-relative path of the document the deviation applies to.
+    document: String,
+    description: String,
+    approved_by: Option<String>,
+}
+
+/// Header fields needed for conformance checks.
+#[derive(Debug, Deserialize)]
+struct ConformanceHeader {
+    constitutional_conformance: Option<ConstitutionalConformance>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ConstitutionalConformance {
+    constitution_version: Option<serde_yaml::Value>,
+    constitution_hash: Option<serde_yaml::Value>,
+    zero_point_version: Option<serde_yaml::Value>,
+    zero_point_hash: Option<serde_yaml::Value>,
+    #[serde(default)]
+    deviations: Option<Vec<String>>,
+    last_verified: Option<serde_yaml::Value>,
+    verified_by: Option<serde_yaml::Value>,
+}
+
+/// Outcome of checking one document.
+#[derive(Default)]
+struct DocumentOutcome {
+    declared: bool,
+    failures: Vec<String>,
+    warnings: Vec<String>,
+}
+
+/// Load a JSON file if it exists.
+fn load_json<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<Option<T>, ConformanceError> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(path)?;
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| ConformanceError::Json(path.to_path_buf(), e))
+}
+
+/// Check one declared reference (Constitution or Zero Point) against the vendored copies.
+///
+/// `vendored` maps (kind, version) to the verified hash of the vendored copy.
+fn check_reference(
+    kind: &str,
+    version: &Option<serde_yaml::Value>,
+    hash: &Option<serde_yaml::Value>,
+    vendored: &BTreeMap<(String, String), String>,
+    outcome: &mut DocumentOutcome,
+) {
+    match (get_yaml_string(version), get_yaml_string(hash)) {
+        (None, None) => {}
+        (Some(_), None) => outcome.failures.push(format!("{}_version is set but {}_hash is null", kind, kind)),
+        (None, Some(_)) => outcome.failures.push(format!("{}_hash is set but {}_version is null", kind, kind)),
+        (Some(v), Some(h)) => {
+            outcome.declared = true;
+            match vendored.get(&(kind.to_string(), v.to_string())) {
+                None => outcome
+                    .failures
+                    .push(format!("{} {} is not vendored in {}", kind, v, VENDORED_DIR)),
+                Some(expected) if !expected.eq_ignore_ascii_case(h) => outcome.failures.push(format!(
+                    "{}_hash does not match vendored {} {} ({}...)",
+                    kind,
+                    kind,
+                    v,
+                    &expected[..16.min(expected.len())]
+                )),
+                Some(_) => {}
+            }
+        }
+    }
+}
+
+/// Check one document's conformance block.
+fn check_document(
+    rel: &str,
+    conformance: &ConstitutionalConformance,
+    vendored: &BTreeMap<(String, String), String>,
+    register: Option<&DeviationRegister>,
+    now: DateTime<Utc>,
+    max_age: Duration,
+) -> DocumentOutcome {
+    let mut outcome = DocumentOutcome::default();
+
+    check_reference(
+        "constitution",
+        &conformance.constitution_version,
+        &conformance.constitution_hash,
+        vendored,
+        &mut outcome,
+    );
+    check_reference(
+        "zero_point",
+        &conformance.zero_point_version,
+        &conformance.zero_point_hash,
+        vendored,
+        &mut outcome,
+    );
+
+    match get_yaml_string(&conformance.last_verified) {
+        None if outcome.declared => outcome.warnings.push("conformance declared but last_verified is null".to_string()),
+        None => {}
+        Some(ts) => match header::parse_utc_timestamp(ts) {
+            Err(e) => outcome.failures.push(format!("last_verified: {}", e)),
+            Ok(verified) => {
+                if verified > now {
+                    outcome.failures.push(format!("last_verified is in the future: {}", ts));
+                } else if now - verified > max_age {
+                    outcome.warnings.push(format!(
+                        "last_verified is stale: {} ({} days old, limit {})",
+                        ts,
+                        (now - verified).num_days(),
+                        max_age.num_days()
+                    ));
+                }
+                if get_yaml_string(&conformance.verified_by).is_none() {
+                    outcome.warnings.push("last_verified is set but verified_by is null".to_string());
+                }
+            }
+        },
+    }
+
+    for deviation in conformance.deviations.iter().flatten() {
+        match register.and_then(|r| r.deviations.get(deviation)) {
+            None => outcome
+                .failures
+                .push(format!("deviation {} is not recorded in {}", deviation, DEVIATION_REGISTER_PATH)),
+            Some(entry) if entry.document != rel => outcome.failures.push(format!(
+                "deviation {} is recorded for {}, not this document",
+                deviation, entry.document
+            )),
+            Some(entry) if entry.description.trim().is_empty() => {
+                outcome.failures.push(format!("deviation {} has no description", deviation));
+            }
+            Some(entry) if entry.approved_by.is_none() => {
+                outcome.failures.push(format!("deviation {} is not approved", deviation));
+            }
+            Some(_) => {}
+        }
+    }
+
+    outcome
+}
+
+/// The `constitutional_conformance` block of a document, or why it cannot be read.
+fn conformance_block(content: &str) -> Result<ConstitutionalConformance, String> {
+    let frontmatter = extract_frontmatter(content).ok_or("missing YAML frontmatter")?;
+    let header: ConformanceHeader =
+        serde_yaml::from_str(frontmatter).map_err(|e| format!("invalid YAML frontmatter: {}", e))?;
+    header
+        .constitutional_conformance
+        .ok_or_else(|| "missing constitutional_conformance section".to_string())
+}
+
+/// Run the `conformance verify` command.
+///
+/// With `strict`, documents that declare no conformance fail instead of
+/// being reported as warnings.
+pub fn verify(
+    repo_root_arg: Option<String>,
+    file_path: Option<String>,
+    max_age_days: i64,
+    strict: bool,
+) -> Result<(), ConformanceError> {
+    let repo_root = match repo_root_arg {
+        Some(path) => PathBuf::from(path),
+        None => find_repo_root()?.ok_or(ConformanceError::NoSpecsDir)?,
+    };
+    let now = Utc::now();
+    let max_age = Duration::days(max_age_days);
+
+    println!("=== XONAIX CONSTITUTIONAL CONFORMANCE VERIFICATION ===");
+    println!("Repository: {}", repo_root.display());
+    println!("last_verified limit: {} days", max_age_days);
+    println!("Mode: {}", if strict { "strict" } else { "standard" });
+    println!();
+
+    // Vendored copies must match their own index before headers are compared with them
+    println!("[1/2] Checking vendored constitutional documents...");
+    let vendored_dir = repo_root.join(VENDORED_DIR);
+    let mut vendored: BTreeMap<(String, String), String> = BTreeMap::new();
+    let mut vendored_failures = 0u32;
+    let index_path = vendored_dir.join(VENDORED_INDEX);
+    match load_json::<VendoredIndex>(&index_path)? {
+        None => {
+            println!("FAIL: no vendored index at {}/{}", VENDORED_DIR, VENDORED_INDEX);
+            vendored_failures += 1;
+        }
+        Some(index) if index.index_version != VENDORED_INDEX_VERSION => {
+            return Err(ConformanceError::UnsupportedVersion(index_path, index.index_version, VENDORED_INDEX_VERSION));
+        }
+        Some(index) => {
+            for doc in &index.documents {
+                let path = vendored_dir.join(&doc.path);
+                let label = format!("{} {} ({})", doc.kind, doc.version, doc.path);
+                let hash_alg = match HashAlg::from_name(&doc.hash_alg) {
+                    Some(alg) => alg,
+                    None => {
+                        println!("FAIL: {}: unsupported hash_alg {}", label, doc.hash_alg);
+                        vendored_failures += 1;
+                        continue;
+                    }
+                };
+                if !path.exists() {
+                    println!("FAIL: {}: file not found", label);
+                    vendored_failures += 1;
+                    continue;
+                }
+                let computed = manifest::compute_file_hash(&path, hash_alg)?;
+                if !computed.eq_ignore_ascii_case(&doc.hash) {
+                    println!("FAIL: {}: vendored copy does not match its indexed hash", label);
+                    vendored_failures += 1;
+                    continue;
+                }
+                println!("PASS: {}", label);
+                let _ = vendored.insert((doc.kind.clone(), doc.version.clone()), computed);
+            }
+        }
+    }
+
+    let register_path = repo_root.join(DEVIATION_REGISTER_PATH);
+    let register: Option<DeviationRegister> = load_json(&register_path)?;
+    if let Some(r) = &register
+        && r.register_version != DEVIATION_REGISTER_VERSION
+    {
+        return Err(ConformanceError::UnsupportedVersion(
+            register_path,
+            r.register_version.clone(),
+            DEVIATION_REGISTER_VERSION,
+        ));
+    }
+
+    println!();
+    println!("[2/2] Checking document conformance...");
+    let documents: Vec<PathBuf> = match file_path {
+        Some(file) => vec![PathBuf::from(file)],
+        None => WalkDir::new(repo_root.join("specs"))
+            .sort_by_file_name()
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .map(|e| e.into_path())
+            .filter(|p| p.is_file() && !should_exclude(p))
+            .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("md"))
+            .collect(),
+    };
+
+    let mut verified = 0u32;
+    let mut undeclared = 0u32;
+    let mut warned = 0u32;
+    let mut failed = 0u32;
+
+    for path in &documents {
+        let content = fs::read_to_string(path)?;
+        let rel = relative_path(path, &repo_root);
+        let conformance = match conformance_block(&content) {
+            Ok(c) => c,
+            Err(reason) => {
+                println!("FAIL: {}: {}", rel, reason);
+                failed += 1;
+                continue;
+            }
+        };
+
+        let outcome = check_document(&rel, &conformance, &vendored, register.as_ref(), now, max_age);
+        for warning in &outcome.warnings {
+            println!("WARN: {}: {}", rel, warning);
+        }
+        if !outcome.warnings.is_empty() {
+            warned += 1;
+        }
+
+        if !outcome.failures.is_empty() {
+            for failure in &outcome.failures {
+                println!("FAIL: {}: {}", rel, failure);
+            }
+            failed += 1;
+        } else if outcome.declared {
+            println!("PASS: {}", rel);
+            verified += 1;
+        } else if strict {
+            println!("FAIL: {}: constitutional conformance not declared", rel);
+            undeclared += 1;
+            failed += 1;
+        } else {
+            println!("WARN: {}: constitutional conformance not declared", rel);
+            undeclared += 1;
+        }
+    }
+
+    println!();
+    println!("=== CONFORMANCE VERIFICATION COMPLETE ===");
+    println!("Vendored documents failed: {}", vendored_failures);
+    println!("Verified: {}", verified);
+    println!("Not declared: {}", undeclared);
+    println!("With warnings: {}", warned);
+    println!("Failed: {}", failed);
+
+    let total_failed = failed + vendored_failures;
+    if total_failed > 0 {
+        Err(ConformanceError::VerificationFailed(total_failed))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: &str = "2026-01-01T00:00:00Z";
+
+    fn block(yaml: &str) -> Result<ConstitutionalConformance, String> {
+        conformance_block(&format!("---\n{}\n---\nbody\n", yaml))
+    }
+
+    fn outcome(yaml: &str, register: Option<&DeviationRegister>) -> DocumentOutcome {
+        let now = DateTime::parse_from_rfc3339(NOW).map(|t| t.with_timezone(&Utc)).unwrap_or_default();
+        match block(yaml) {
+            Ok(c) => check_document("specs/DOC.md", &c, &BTreeMap::new(), register, now, Duration::days(365)),
+            Err(e) => DocumentOutcome {
+                failures: vec![e],
+                ..DocumentOutcome::default()
+            },
+        }
+    }
+
+    /// Regression: parse errors used to be reported as a missing section.
+    #[test]
+    fn reports_parse_errors() {
+        assert_eq!(conformance_block("no header").err().as_deref(), Some("missing YAML frontmatter"));
+        assert!(block("constitutional_conformance: [unclosed").is_err_and(|e| e.starts_with("invalid YAML frontmatter")));
+        assert_eq!(block("title: x").err().as_deref(), Some("missing constitutional_conformance section"));
+    }
+
+    #[test]
+    fn all_null_block_is_not_declared() {
+        let result = outcome("constitutional_conformance:\n  constitution_version: null\n  constitution_hash: null", None);
+        assert!(!result.declared);
+        assert!(result.failures.is_empty());
+    }
+
+    #[test]
+    fn unvendored_reference_fails() {
+        let result = outcome("constitutional_conformance:\n  constitution_version: \"1.0.0\"\n  constitution_hash: \"ab\"", None);
+        assert!(result.declared);
+        assert!(result.failures.iter().any(|f| f.contains("not vendored")));
+    }
+
+    /// Regression: offsets were accepted here although header-validate requires UTC.
+    #[test]
+    fn last_verified_must_be_utc() {
+        let failures = |ts: &str| {
+            outcome(&format!("constitutional_conformance:\n  last_verified: \"{}\"\n  verified_by: \"founder\"", ts), None).failures
+        };
+        assert!(failures("2025-12-01T00:00:00Z").is_empty());
+        assert!(failures("2025-12-01T00:00:00+00:00").iter().any(|f| f.contains("not UTC")));
+        assert!(failures("2025-12-01").iter().any(|f| f.contains("not an RFC 3339 timestamp")));
+    }
+
+    #[test]
+    fn deviations_must_be_recorded_and_approved() {
+        let entry = |document: &str, approved_by: Option<&str>| DeviationEntry {
+            document: document.to_string(),
+            description: "Allowed exception".to_string(),
+            approved_by: approved_by.map(str::to_string),
+        };
+        let register = DeviationRegister {
+            register_version: DEVIATION_REGISTER_VERSION.to_string(),
+            deviations: BTreeMap::from([
+                ("DEV-1".to_string(), entry("specs/DOC.md", Some("founder"))),
+                ("DEV-2".to_string(), entry("specs/DOC.md", None)),
+                ("DEV-3".to_string(), entry("specs/OTHER.md", Some("founder"))),
+            ]),
+        };
+        let failures = |id: &str| {
+            outcome(&format!("constitutional_conformance:\n  deviations: [\"{}\"]", id), Some(&register)).failures
+        };
+        assert!(failures("DEV-1").is_empty());
+        assert!(failures("DEV-2").iter().any(|f| f.contains("not approved")));
+        assert!(failures("DEV-3").iter().any(|f| f.contains("not this document")));
+        assert!(failures("DEV-4").iter().any(|f| f.contains("not recorded")));
+    }
+}
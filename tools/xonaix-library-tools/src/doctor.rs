@@ -2,12 +2,15 @@
 //!
 //! Performs comprehensive environment verification for the Library.
 
-use serde::Deserialize;
 use sha2::{Digest, Sha256};
 use std::fs;
 use std::path::PathBuf;
 use thiserror::Error;
 
+use crate::common::find_repo_root;
+use crate::keyring;
+use crate::unit::UnitRegistry;
+
 /// Errors during doctor checks.
 #[derive(Debug, Error)]
 pub enum DoctorError {
@@ -24,50 +27,11 @@ pub enum DoctorError {
     DoctorFailed(u32),
 }
 
-/// UNIT_REGISTRY.json structure
-#[derive(Debug, Deserialize)]
-struct UnitRegistry {
-    #[allow(dead_code)]
-    registry_version: String,
-    #[allow(dead_code)]
-    description: String,
-    units: std::collections::HashMap<String, RegistryEntry>,
-}
-
-#[derive(Debug, Deserialize)]
-struct RegistryEntry {
-    path: String,
-    #[allow(dead_code)]
-    domain: String,
-    #[allow(dead_code)]
-    status: String,
-    #[allow(dead_code)]
-    description: String,
-}
-
-/// Find repository root.
-fn find_repo_root() -> Result<PathBuf, DoctorError> {
-    let mut current = std::env::current_dir()?;
-
-    if current.join("specs").exists() {
-        return Ok(current);
-    }
-
-    while let Some(parent) = current.parent() {
-        if parent.join("specs").exists() {
-            return Ok(parent.to_path_buf());
-        }
-        current = parent.to_path_buf();
-    }
-
-    Err(DoctorError::NoSpecsDir)
-}
-
 /// Run all doctor checks.
 pub fn run(repo_root_arg: Option<String>) -> Result<(), DoctorError> {
     let repo_root = match repo_root_arg {
         Some(path) => PathBuf::from(path),
-        None => find_repo_root()?,
+        None => find_repo_root()?.ok_or(DoctorError::NoSpecsDir)?,
     };
 
     let specs_dir = repo_root.join("specs");
@@ -80,12 +44,13 @@ pub fn run(repo_root_arg: Option<String>) -> Result<(), DoctorError> {
     let mut failed_checks = 0u32;
 
     // Check 1: Required governance files exist
-    println!("[1/5] Checking required governance files...");
+    println!("[1/6] Checking required governance files...");
     // Only library-specific governance files required here.
     // Org-wide governance lives in xonaix-specs.
     let required_files = [
         "LIBRARY_SEALING_CONTRACT.md",
         "LIBRARY_STANDARD_HEADER_CONTRACT.md",
+        "SIGNER_KEYRING.json",
         "UNIT_REGISTRY.json",
     ];
 
@@ -109,7 +74,7 @@ pub fn run(repo_root_arg: Option<String>) -> Result<(), DoctorError> {
 
     // Check 2: UNIT_REGISTRY.json is valid
     println!();
-    println!("[2/5] Validating UNIT_REGISTRY.json...");
+    println!("[2/6] Validating UNIT_REGISTRY.json...");
     let registry_path = governance_dir.join("UNIT_REGISTRY.json");
     let registry: Option<UnitRegistry> = if registry_path.exists() {
         match fs::read_to_string(&registry_path) {
@@ -138,7 +103,7 @@ pub fn run(repo_root_arg: Option<String>) -> Result<(), DoctorError> {
 
     // Check 3: Required directories exist
     println!();
-    println!("[3/5] Checking required directories...");
+    println!("[3/6] Checking required directories...");
     let required_dirs = ["standards", "meta"];
     let mut missing_dirs: Vec<String> = Vec::new();
 
@@ -161,7 +126,7 @@ pub fn run(repo_root_arg: Option<String>) -> Result<(), DoctorError> {
 
     // Check 4: Unit paths exist
     println!();
-    println!("[4/5] Checking unit paths...");
+    println!("[4/6] Checking unit paths...");
     if let Some(ref reg) = registry {
         let mut unit_errors: Vec<String> = Vec::new();
         for (unit_id, entry) in &reg.units {
@@ -186,7 +151,7 @@ pub fn run(repo_root_arg: Option<String>) -> Result<(), DoctorError> {
 
     // Check 5: Hash computation is functional
     println!();
-    println!("[5/5] Verifying hash computation...");
+    println!("[5/6] Verifying hash computation...");
     let test_data = b"xonaix-library-tools doctor test";
     let mut hasher = Sha256::new();
     hasher.update(test_data);
@@ -200,6 +165,27 @@ pub fn run(repo_root_arg: Option<String>) -> Result<(), DoctorError> {
         failed_checks += 1;
     }
 
+    // Check 6: Signer keyring is valid
+    println!();
+    println!("[6/6] Validating SIGNER_KEYRING.json...");
+    match keyring::load_keyring(&repo_root, None) {
+        Ok(kr) => {
+            let problems = kr.validate();
+            if problems.is_empty() {
+                println!("PASS: SIGNER_KEYRING.json is valid ({} signer(s))", kr.signers.len());
+            } else {
+                for p in &problems {
+                    println!("  FAIL: {}", p);
+                }
+                failed_checks += 1;
+            }
+        }
+        Err(e) => {
+            println!("FAIL: {}", e);
+            failed_checks += 1;
+        }
+    }
+
     println!();
     println!("=== DOCTOR COMPLETE ===");
     if failed_checks == 0 {
@@ -8,6 +8,8 @@ use std::path::{Path, PathBuf};
 use thiserror::Error;
 use walkdir::WalkDir;
 
+use crate::common::{find_repo_root, should_exclude};
+
 /// Errors during enforcement.
 #[derive(Debug, Error)]
 pub enum EnforceError {
@@ -24,56 +26,6 @@ pub enum EnforceError {
     EnforcementFailed(u32),
 }
 
-/// Find repository root.
-fn find_repo_root() -> Result<PathBuf, EnforceError> {
-    let mut current = std::env::current_dir()?;
-
-    if current.join("specs").exists() {
-        return Ok(current);
-    }
-
-    while let Some(parent) = current.parent() {
-        if parent.join("specs").exists() {
-            return Ok(parent.to_path_buf());
-        }
-        current = parent.to_path_buf();
-    }
-
-    Err(EnforceError::NoSpecsDir)
-}
-
-/// Check if path should be excluded from enforcement.
-fn should_exclude(path: &Path) -> bool {
-    let path_str = path.to_string_lossy();
-
-    // Exclude _deprecated/
-    if path_str.contains("_deprecated/") || path_str.contains("_deprecated\\") {
-        return true;
-    }
-
-    // Exclude _reference/
-    if path_str.contains("_reference/") || path_str.contains("_reference\\") {
-        return true;
-    }
-
-    // Exclude .git/
-    if path_str.contains(".git/") || path_str.contains(".git\\") {
-        return true;
-    }
-
-    // Exclude target/
-    if path_str.contains("/target/") || path_str.contains("\\target\\") {
-        return true;
-    }
-
-    // Exclude manifests/ (generated files)
-    if path_str.contains("manifests/") || path_str.contains("manifests\\") {
-        return true;
-    }
-
-    false
-}
-
 /// Check if path is a governance file (excluded from token checks only).
 /// Governance files describe rules and necessarily mention forbidden tokens.
 fn is_governance_file(path: &Path) -> bool {
@@ -85,7 +37,7 @@ fn is_governance_file(path: &Path) -> bool {
 pub fn run(repo_root_arg: Option<String>, _current_only: bool) -> Result<(), EnforceError> {
     let repo_root = match repo_root_arg {
         Some(path) => PathBuf::from(path),
-        None => find_repo_root()?,
+        None => find_repo_root()?.ok_or(EnforceError::NoSpecsDir)?,
     };
 
     let specs_dir = repo_root.join("specs");
@@ -236,10 +188,10 @@ pub fn run(repo_root_arg: Option<String>, _current_only: bool) -> Result<(), Enf
             continue;
         }
 
-        if let Ok(content) = fs::read_to_string(path) {
-            if emoji_re.is_match(&content) {
-                emoji_violations.push(format!("{}: Contains emoji", path.display()));
-            }
+        if let Ok(content) = fs::read_to_string(path)
+            && emoji_re.is_match(&content)
+        {
+            emoji_violations.push(format!("{}: Contains emoji", path.display()));
         }
     }
 
@@ -324,10 +276,10 @@ pub fn run(repo_root_arg: Option<String>, _current_only: bool) -> Result<(), Enf
             continue;
         }
 
-        if let Ok(content) = fs::read_to_string(path) {
-            if content.contains("\r\n") {
-                crlf_violations.push(format!("{}: Contains CRLF", path.display()));
-            }
+        if let Ok(content) = fs::read_to_string(path)
+            && content.contains("\r\n")
+        {
+            crlf_violations.push(format!("{}: Contains CRLF", path.display()));
         }
     }
 
@@ -0,0 +1,573 @@
+//! Engagement registry module.
+//!
+//! Validates the engagement registry sketched in MULTI_PARTY_GOVERNANCE_SPEC
+//! §6 and computes each engagement's readiness from the headers of its
+//! required specs, rather than from the hand-maintained `readiness` block.
+//!
+//! A document only counts as sealed when its signature and approvals verify
+//! against the signer keyring; without a keyring nothing is sealed.
+
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use walkdir::WalkDir;
+
+use crate::approval::{self, Approval, ApprovalEvaluation, ApprovalRequirements};
+use crate::common::{extract_frontmatter, find_repo_root, get_yaml_string};
+use crate::keyring::{self, Keyring};
+use crate::seal;
+use crate::sign;
+use crate::unit::{self, UnitError, UnitRegistry};
+
+/// Repository-relative path of the engagement registry.
+pub(crate) const ENGAGEMENT_REGISTRY_PATH: &str = "specs/_governance/ENGAGEMENT_REGISTRY.json";
+
+/// Supported engagement registry version.
+const ENGAGEMENT_REGISTRY_VERSION: &str = "1.0.0";
+
+/// Valid engagement statuses.
+const VALID_ENGAGEMENT_STATUSES: &[&str] = &["proposed", "active", "completed", "cancelled"];
+
+/// Party that seals single-party (v2.1) documents through `integrity.signature`.
+const LIBRARY_PARTY: &str = "xonaix";
+
+/// Errors during engagement operations.
+#[derive(Debug, Error)]
+pub enum EngagementError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error(transparent)]
+    Unit(#[from] UnitError),
+
+    #[error("Cannot find specs/ directory")]
+    NoSpecsDir,
+
+    #[error("Engagement registry not found: {0}")]
+    RegistryNotFound(PathBuf),
+
+    #[error("Unsupported engagement registry version {0} (expected {1})")]
+    UnsupportedVersion(String, &'static str),
+
+    #[error("Engagement not found: {0}")]
+    EngagementNotFound(String),
+
+    #[error("Engagement registry validation failed: {0} error(s)")]
+    ValidationFailed(u32),
+}
+
+/// ENGAGEMENT_REGISTRY.json schema
+///
+/// Fields are declared in file order; `readiness --write` serializes the
+/// registry back through these types.
+#[derive(Debug, Deserialize, Serialize)]
+pub(crate) struct EngagementRegistry {
+    pub registry_version: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub engagements: BTreeMap<String, Engagement>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub(crate) struct Engagement {
+    pub customer: String,
+    pub status: String,
+    pub created: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contract_ref: Option<String>,
+    pub required_specs: Vec<String>,
+    #[serde(default)]
+    pub approval_matrix: BTreeMap<String, MatrixEntry>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub readiness: Option<Readiness>,
+}
+
+/// Parties whose approval a required spec needs.
+#[derive(Debug, Deserialize, Serialize)]
+pub(crate) struct MatrixEntry {
+    #[serde(default)]
+    pub required: Vec<String>,
+    #[serde(default)]
+    pub optional: Vec<String>,
+}
+
+/// The recorded (or computed) readiness of an engagement.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub(crate) struct Readiness {
+    pub all_required_sealed: bool,
+    pub blocking_documents: Vec<String>,
+}
+
+/// Header fields needed to decide whether a document is sealed.
+#[derive(Debug, Deserialize)]
+struct SealHeader {
+    status: Option<String>,
+    integrity: Option<SealIntegrity>,
+    approvals: Option<Vec<Approval>>,
+    approval_requirements: Option<ApprovalRequirements>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SealIntegrity {
+    content_hash: Option<serde_yaml::Value>,
+}
+
+/// Seal state of one document in a unit.
+#[derive(Debug)]
+pub(crate) struct DocumentState {
+    pub path: PathBuf,
+    pub status: String,
+    pub content_hash: Option<String>,
+    /// Signer of a signature that verified against the keyring.
+    pub signed_by: Option<String>,
+    /// Why the document does not count as sealed; empty when it does.
+    pub problems: Vec<String>,
+}
+
+/// Load the repository's signer keyring, reporting why it is unavailable.
+fn load_keyring(repo_root: &Path) -> Option<Keyring> {
+    match keyring::load_keyring(repo_root, None) {
+        Ok(kr) => Some(kr),
+        Err(e) => {
+            println!("WARN: {} (no document counts as sealed)", e);
+            println!();
+            None
+        }
+    }
+}
+
+/// Load the engagement registry from an explicit path, or from the repository default.
+pub(crate) fn load_registry(repo_root: &Path, path: Option<&Path>) -> Result<EngagementRegistry, EngagementError> {
+    let registry_path = match path {
+        Some(p) => p.to_path_buf(),
+        None => repo_root.join(ENGAGEMENT_REGISTRY_PATH),
+    };
+    if !registry_path.exists() {
+        return Err(EngagementError::RegistryNotFound(registry_path));
+    }
+
+    let registry: EngagementRegistry = serde_json::from_str(&fs::read_to_string(&registry_path)?)?;
+    if registry.registry_version != ENGAGEMENT_REGISTRY_VERSION {
+        return Err(EngagementError::UnsupportedVersion(
+            registry.registry_version,
+            ENGAGEMENT_REGISTRY_VERSION,
+        ));
+    }
+
+    Ok(registry)
+}
+
+/// Whether a party or role named in an approval matrix has sealed a document.
+///
+/// With an `approvals` block, only approvals that verified count. Documents
+/// without one follow the single-party model: only the library party can
+/// seal them, through a verified `integrity.signature`.
+fn party_has_sealed(approvals: Option<&ApprovalEvaluation>, signature_verified: bool, party: &str) -> bool {
+    match approvals {
+        Some(eval) => eval
+            .signed_parties
+            .iter()
+            .chain(&eval.signed_roles)
+            .any(|name| name.eq_ignore_ascii_case(party)),
+        None => party.eq_ignore_ascii_case(LIBRARY_PARTY) && signature_verified,
+    }
+}
+
+/// Inspect one document's header and decide whether it is sealed.
+fn document_state(
+    path: &Path,
+    content: &str,
+    keyring: Option<&Keyring>,
+    required_parties: &[String],
+) -> DocumentState {
+    let mut state = DocumentState {
+        path: path.to_path_buf(),
+        status: String::new(),
+        content_hash: None,
+        signed_by: None,
+        problems: Vec::new(),
+    };
+
+    let header: SealHeader = match extract_frontmatter(content).map(serde_yaml::from_str) {
+        Some(Ok(h)) => h,
+        _ => {
+            state.problems.push("missing or invalid YAML frontmatter".to_string());
+            return state;
+        }
+    };
+
+    state.status = header.status.clone().unwrap_or_default();
+    if state.status != "sealed" {
+        state.problems.push(format!("status is '{}'", state.status));
+    }
+
+    let integrity = header.integrity.as_ref();
+    let stored = integrity.and_then(|i| get_yaml_string(&i.content_hash));
+    let computed = seal::compute_content_hash(content);
+    match (stored, &computed) {
+        (Some(s), Some(c)) if s.eq_ignore_ascii_case(c) => state.content_hash = Some(c.clone()),
+        (Some(_), _) => state.problems.push("content_hash does not match document body".to_string()),
+        (None, _) => state.problems.push("content_hash missing".to_string()),
+    }
+
+    let keyring = match keyring {
+        Some(kr) => kr,
+        None => {
+            state.problems.push("signature not verified: signer keyring could not be loaded".to_string());
+            return state;
+        }
+    };
+
+    match sign::verify_document_signature(path, content, keyring) {
+        Ok(Some(verified)) => state.signed_by = Some(verified.signer),
+        Ok(None) => state.problems.push("signature missing".to_string()),
+        Err(reason) => state.problems.push(format!("signature invalid: {}", reason)),
+    }
+
+    let approvals = match (&header.approvals, &computed) {
+        (Some(approvals), Some(hash)) => {
+            let eval = approval::evaluate(approvals, header.approval_requirements.as_ref(), hash, Some(keyring));
+            state.problems.extend(eval.errors.iter().map(|e| format!("approval invalid: {}", e)));
+            state.problems.extend(eval.unmet.iter().map(|u| format!("approval requirements not met: {}", u)));
+            Some(eval)
+        }
+        _ => None,
+    };
+
+    for party in required_parties {
+        if !party_has_sealed(approvals.as_ref(), state.signed_by.is_some(), party) {
+            state.problems.push(format!("no sealed approval from '{}'", party));
+        }
+    }
+
+    state
+}
+
+/// Seal state of every document in a registered unit.
+///
+/// `required_parties` are parties (or roles) that must each have sealed
+/// every document, as listed in an engagement's approval matrix.
+pub(crate) fn unit_documents(
+    repo_root: &Path,
+    registry: &UnitRegistry,
+    unit_id: &str,
+    keyring: Option<&Keyring>,
+    required_parties: &[String],
+) -> Result<Option<Vec<DocumentState>>, EngagementError> {
+    let entry = match registry.units.get(unit_id) {
+        Some(e) => e,
+        None => return Ok(None),
+    };
+
+    let mut documents = Vec::new();
+    for entry in WalkDir::new(repo_root.join(&entry.path))
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|e| e.ok())
+    {
+        let path = entry.path();
+        if !path.is_file() || path.extension().and_then(|e| e.to_str()) != Some("md") {
+            continue;
+        }
+        let content = fs::read_to_string(path)?;
+        documents.push(document_state(path, &content, keyring, required_parties));
+    }
+
+    Ok(Some(documents))
+}
+
+/// Compute an engagement's readiness from the current headers.
+///
+/// A required spec blocks the engagement when it has no documents or any of
+/// its documents is not sealed by every party its matrix entry requires.
+fn compute_readiness(
+    repo_root: &Path,
+    units: &UnitRegistry,
+    keyring: Option<&Keyring>,
+    engagement: &Engagement,
+) -> Result<(Readiness, BTreeMap<String, Vec<String>>), EngagementError> {
+    let mut blocking = Vec::new();
+    let mut reasons: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+    for unit_id in &engagement.required_specs {
+        let required: &[String] = engagement
+            .approval_matrix
+            .get(unit_id)
+            .map(|m| m.required.as_slice())
+            .unwrap_or_default();
+
+        let unit_reasons: Vec<String> = match unit_documents(repo_root, units, unit_id, keyring, required)? {
+            None => vec!["not in UNIT_REGISTRY.json".to_string()],
+            Some(docs) if docs.is_empty() => vec!["unit has no documents".to_string()],
+            Some(docs) => docs
+                .iter()
+                .flat_map(|d| {
+                    let rel = d.path.strip_prefix(repo_root).unwrap_or(&d.path).display().to_string();
+                    d.problems.iter().map(move |p| format!("{}: {}", rel, p))
+                })
+                .collect(),
+        };
+
+        if !unit_reasons.is_empty() {
+            blocking.push(unit_id.clone());
+            let _ = reasons.insert(unit_id.clone(), unit_reasons);
+        }
+    }
+
+    Ok((
+        Readiness {
+            all_required_sealed: blocking.is_empty(),
+            blocking_documents: blocking,
+        },
+        reasons,
+    ))
+}
+
+/// Structural problems in one engagement entry.
+fn validate_engagement(id: &str, engagement: &Engagement, units: &UnitRegistry) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    if engagement.customer.trim().is_empty() {
+        problems.push(format!("{}: customer is empty", id));
+    }
+    if !VALID_ENGAGEMENT_STATUSES.contains(&engagement.status.as_str()) {
+        problems.push(format!("{}: invalid status '{}'", id, engagement.status));
+    }
+    if DateTime::parse_from_rfc3339(&engagement.created).is_err() {
+        problems.push(format!("{}: created is not an RFC 3339 timestamp: {}", id, engagement.created));
+    }
+    if engagement.required_specs.is_empty() {
+        problems.push(format!("{}: required_specs is empty", id));
+    }
+
+    let mut seen = Vec::new();
+    for unit_id in &engagement.required_specs {
+        if seen.contains(&unit_id) {
+            problems.push(format!("{}: {} listed twice in required_specs", id, unit_id));
+        }
+        seen.push(unit_id);
+
+        if units.deprecated.contains(unit_id) {
+            problems.push(format!("{}: required spec {} is deprecated", id, unit_id));
+        } else if units.reserved.contains(unit_id) {
+            problems.push(format!("{}: required spec {} is reserved, not registered", id, unit_id));
+        } else if !units.units.contains_key(unit_id) {
+            problems.push(format!("{}: required spec {} is not in UNIT_REGISTRY.json", id, unit_id));
+        }
+    }
+
+    for (unit_id, entry) in &engagement.approval_matrix {
+        if !engagement.required_specs.contains(unit_id) {
+            problems.push(format!("{}: approval_matrix entry {} is not a required spec", id, unit_id));
+        }
+        for party in &entry.required {
+            if entry.optional.contains(party) {
+                problems.push(format!("{}: {} lists '{}' as both required and optional", id, unit_id, party));
+            }
+        }
+    }
+
+    problems
+}
+
+/// Run the `engagement validate` command.
+pub fn validate(repo_root_arg: Option<String>, registry_path: Option<String>) -> Result<(), EngagementError> {
+    let repo_root = match repo_root_arg {
+        Some(path) => PathBuf::from(path),
+        None => find_repo_root()?.ok_or(EngagementError::NoSpecsDir)?,
+    };
+
+    println!("=== XONAIX ENGAGEMENT REGISTRY VALIDATION ===");
+    println!("Repository: {}", repo_root.display());
+    println!();
+
+    let registry = load_registry(&repo_root, registry_path.as_deref().map(Path::new))?;
+    let units = unit::load_registry(&repo_root)?;
+    let keyring = load_keyring(&repo_root);
+
+    let mut errors = 0u32;
+    let mut warnings = 0u32;
+
+    for (id, engagement) in &registry.engagements {
+        let problems = validate_engagement(id, engagement, &units);
+        if problems.is_empty() {
+            println!("PASS: {} ({}, {} required spec(s))", id, engagement.customer, engagement.required_specs.len());
+        }
+        for problem in &problems {
+            println!("FAIL: {}", problem);
+        }
+        errors += problems.len() as u32;
+
+        // A recorded readiness block must agree with the headers
+        if let Some(recorded) = &engagement.readiness {
+            let (computed, _) = compute_readiness(&repo_root, &units, keyring.as_ref(), engagement)?;
+            if *recorded != computed {
+                println!("WARN: {}: recorded readiness is stale (run `engagement readiness --write`)", id);
+                warnings += 1;
+            }
+        }
+    }
+
+    println!();
+    println!("=== VALIDATION COMPLETE ===");
+    println!("Engagements: {}", registry.engagements.len());
+    println!("Errors: {}", errors);
+    println!("Warnings: {}", warnings);
+
+    if errors > 0 {
+        Err(EngagementError::ValidationFailed(errors))
+    } else {
+        Ok(())
+    }
+}
+
+/// Run the `engagement readiness` command.
+///
+/// With `write`, the computed readiness replaces each engagement's
+/// `readiness` block in the registry file.
+pub fn readiness(
+    repo_root_arg: Option<String>,
+    registry_path: Option<String>,
+    engagement_id: Option<String>,
+    write: bool,
+) -> Result<(), EngagementError> {
+    let repo_root = match repo_root_arg {
+        Some(path) => PathBuf::from(path),
+        None => find_repo_root()?.ok_or(EngagementError::NoSpecsDir)?,
+    };
+    let registry_file = match &registry_path {
+        Some(p) => PathBuf::from(p),
+        None => repo_root.join(ENGAGEMENT_REGISTRY_PATH),
+    };
+
+    let mut registry = load_registry(&repo_root, Some(&registry_file))?;
+    let units = unit::load_registry(&repo_root)?;
+
+    if let Some(id) = &engagement_id
+        && !registry.engagements.contains_key(id)
+    {
+        return Err(EngagementError::EngagementNotFound(id.clone()));
+    }
+
+    println!("=== XONAIX ENGAGEMENT READINESS ===");
+    println!("Repository: {}", repo_root.display());
+    println!();
+
+    let keyring = load_keyring(&repo_root);
+
+    let mut computed_all: BTreeMap<String, Readiness> = BTreeMap::new();
+    for (id, engagement) in &registry.engagements {
+        if engagement_id.as_ref().is_some_and(|e| e != id) {
+            continue;
+        }
+
+        let (computed, reasons) = compute_readiness(&repo_root, &units, keyring.as_ref(), engagement)?;
+        let marker = if computed.all_required_sealed { "READY" } else { "BLOCKED" };
+        println!("{}: {} ({})", marker, id, engagement.customer);
+        for unit_id in &engagement.required_specs {
+            match reasons.get(unit_id) {
+                None => println!("  SEALED:   {}", unit_id),
+                Some(unit_reasons) => {
+                    println!("  BLOCKING: {}", unit_id);
+                    for reason in unit_reasons {
+                        println!("    - {}", reason);
+                    }
+                }
+            }
+        }
+        println!();
+        let _ = computed_all.insert(id.clone(), computed);
+    }
+
+    if write {
+        for (id, computed) in &computed_all {
+            if let Some(engagement) = registry.engagements.get_mut(id) {
+                engagement.readiness = Some(computed.clone());
+            }
+        }
+        fs::write(&registry_file, format!("{}\n", serde_json::to_string_pretty(&registry)?))?;
+        println!("Readiness written to: {}", registry_file.display());
+        println!();
+    }
+
+    let ready = computed_all.values().filter(|r| r.all_required_sealed).count();
+    println!("=== READINESS COMPLETE ===");
+    println!("Ready: {}", ready);
+    println!("Blocked: {}", computed_all.len() - ready);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keyring::tests::test_keyring;
+    use crate::sign::tests::signed_document;
+
+    const SIGNED_AT: &str = "2025-02-01T00:00:00Z";
+
+    fn problems(content: &str, keyring: Option<&Keyring>) -> Vec<String> {
+        let required = [LIBRARY_PARTY.to_string()];
+        document_state(Path::new("DOC.md"), content, keyring, &required).problems
+    }
+
+    #[test]
+    fn verified_signature_seals_for_the_library_party() {
+        let content = signed_document(SIGNED_AT, SIGNED_AT);
+        assert_eq!(problems(&content, Some(&test_keyring(None))), Vec::<String>::new());
+    }
+
+    /// Regression: any signature string used to count as sealed.
+    #[test]
+    fn forged_signature_does_not_seal() {
+        let content = signed_document(SIGNED_AT, SIGNED_AT);
+        let signature = content
+            .lines()
+            .find_map(|l| l.trim().strip_prefix("signature: "))
+            .map(|s| s.trim_matches('"').to_string())
+            .unwrap_or_default();
+        let forged = content.replace(&signature, &"ab".repeat(64));
+        let found = problems(&forged, Some(&test_keyring(None)));
+        assert!(found.iter().any(|p| p.starts_with("signature invalid")), "{:?}", found);
+        assert!(found.iter().any(|p| p.contains("no sealed approval")), "{:?}", found);
+    }
+
+    #[test]
+    fn nothing_is_sealed_without_keyring() {
+        let content = signed_document(SIGNED_AT, SIGNED_AT);
+        assert!(problems(&content, None).iter().any(|p| p.contains("keyring could not be loaded")));
+    }
+
+    /// Regression: `readiness --write` used to re-sort the registry's keys.
+    #[test]
+    fn registry_keeps_field_order() {
+        let json = r#"{
+  "registry_version": "1.0.0",
+  "engagements": {
+    "e1": {
+      "customer": "Bank X",
+      "status": "active",
+      "created": "2025-12-31T00:00:00Z",
+      "contract_ref": "CONTRACT-2025-001",
+      "required_specs": [
+        "library/standards/rust"
+      ],
+      "approval_matrix": {},
+      "readiness": {
+        "all_required_sealed": false,
+        "blocking_documents": []
+      }
+    }
+  }
+}"#;
+        let registry: Result<EngagementRegistry, _> = serde_json::from_str(json);
+        let written = registry.and_then(|r| serde_json::to_string_pretty(&r)).unwrap_or_default();
+        assert_eq!(written, json);
+    }
+}
@@ -0,0 +1,544 @@
+//! Frontmatter normalization module.
+//!
+//! Rewrites document headers into the canonical field order and section
+//! comments of a schema layout (header contract §4.1 and §4.2). This is the
+//! normalize-frontmatter tool of header contract §13.
+//!
+//! Headers are rewritten line by line rather than re-serialized, so values,
+//! quoting, inline comments and the body bytes are preserved. Standalone
+//! comments move with the key that follows them. Keys the layout does not
+//! know (such as v3.0 sections) are kept, with their own section comments,
+//! ahead of the Lifecycle section.
+
+use serde_yaml::Value;
+use std::fs;
+use std::path::PathBuf;
+use thiserror::Error;
+
+use crate::common::{collect_documents, find_repo_root};
+
+/// Errors during frontmatter normalization.
+#[derive(Debug, Error)]
+pub enum FrontmatterError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Cannot find specs/ directory")]
+    NoSpecsDir,
+
+    #[error("Normalization failed: {0} file(s) could not be normalized")]
+    NormalizeFailed(u32),
+
+    #[error("Normalization check failed: {0} file(s) not normalized")]
+    NotNormalized(u32),
+}
+
+/// A header field in a layout.
+pub(crate) struct Field {
+    pub key: &'static str,
+    /// Value written when the field is missing, if the contract allows one.
+    pub default: Option<&'static str>,
+    /// Canonical order of nested fields.
+    pub children: &'static [Field],
+}
+
+/// A group of fields introduced by a `# --- Name ---` comment.
+pub(crate) struct Section {
+    pub comment: Option<&'static str>,
+    pub fields: &'static [Field],
+}
+
+/// The canonical header layout of one schema version.
+pub(crate) struct Layout {
+    pub schema_version: &'static str,
+    pub sections: &'static [Section],
+    /// Index of the section that keys unknown to the layout are placed before.
+    pub extras_before: usize,
+}
+
+const fn field(key: &'static str) -> Field {
+    Field { key, default: None, children: &[] }
+}
+
+const fn nullable(key: &'static str) -> Field {
+    Field { key, default: Some("null"), children: &[] }
+}
+
+const fn list(key: &'static str) -> Field {
+    Field { key, default: Some("[]"), children: &[] }
+}
+
+const fn nested(key: &'static str, children: &'static [Field]) -> Field {
+    Field { key, default: None, children }
+}
+
+/// Schema v2.1 layout (header contract §4.1, field requirements from §6).
+pub(crate) const LAYOUT_V2_1: Layout = Layout {
+    schema_version: "2.1",
+    sections: &[
+        Section {
+            comment: None,
+            fields: &[field("schema"), field("schema_version")],
+        },
+        Section {
+            comment: Some("# --- Identity ---"),
+            fields: &[
+                field("repo"),
+                field("path"),
+                field("unit_id"),
+                field("title"),
+                field("document_type"),
+                field("language"),
+            ],
+        },
+        Section {
+            comment: Some("# --- Version ---"),
+            fields: &[field("version"), nullable("baseline"), field("status")],
+        },
+        Section {
+            comment: Some("# --- Classification ---"),
+            fields: &[nullable("trust_class"), field("classification"), list("compliance")],
+        },
+        Section {
+            comment: Some("# --- Ownership ---"),
+            fields: &[field("owner"), field("approved_by"), field("authority_tier")],
+        },
+        Section {
+            comment: Some("# --- Authority ---"),
+            fields: &[nested("authority", &[field("repo"), field("ref"), nullable("version")])],
+        },
+        Section {
+            comment: Some("# --- Relationships ---"),
+            fields: &[
+                list("depends_on"),
+                nullable("supersedes"),
+                nullable("superseded_by"),
+                list("implements"),
+            ],
+        },
+        Section {
+            comment: Some("# --- Integrity ---"),
+            fields: &[nested(
+                "integrity",
+                &[
+                    nullable("hash_alg"),
+                    nullable("content_hash"),
+                    nullable("signature"),
+                    nullable("signed_by"),
+                    nullable("signed_at"),
+                ],
+            )],
+        },
+        Section {
+            comment: Some("# --- Constitutional Conformance ---"),
+            fields: &[nested(
+                "constitutional_conformance",
+                &[
+                    nullable("constitution_version"),
+                    nullable("constitution_hash"),
+                    nullable("zero_point_version"),
+                    nullable("zero_point_hash"),
+                    list("deviations"),
+                    nullable("last_verified"),
+                    nullable("verified_by"),
+                ],
+            )],
+        },
+        Section {
+            comment: Some("# --- Lifecycle ---"),
+            fields: &[field("created"), field("last_updated")],
+        },
+    ],
+    extras_before: 9,
+};
+
+/// A key and the lines that belong to it.
+struct Entry {
+    key: String,
+    /// Standalone comment lines directly above the key.
+    comments: Vec<String>,
+    /// The key line followed by its continuation lines.
+    lines: Vec<String>,
+    /// The `# --- Name ---` comment of the section the key appeared in.
+    section: Option<String>,
+}
+
+/// A header split into entries.
+struct ParsedHeader {
+    entries: Vec<Entry>,
+    /// Comments after the last key.
+    trailing: Vec<String>,
+}
+
+/// Result of normalizing one document.
+pub(crate) struct Normalized {
+    pub content: String,
+    /// Fields added with their default value.
+    pub filled: Vec<String>,
+    /// Required fields that are missing and have no default.
+    pub missing: Vec<String>,
+}
+
+/// Whether a line is a `# --- Name ---` section comment.
+fn is_section_comment(line: &str) -> bool {
+    let trimmed = line.trim();
+    trimmed.starts_with("# ---") && trimmed.ends_with("---") && trimmed.len() > "# ------".len()
+}
+
+/// The key of a mapping line at `indent` spaces, if the line is one.
+fn key_at(line: &str, indent: usize) -> Option<&str> {
+    let rest = line.strip_prefix(&" ".repeat(indent))?;
+    let first = rest.chars().next()?;
+    if !(first.is_ascii_alphabetic() || first == '_') {
+        return None;
+    }
+    let end = rest.find(':')?;
+    let key = &rest[..end];
+    let after = &rest[end + 1..];
+    let is_key = key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') && (after.is_empty() || after.starts_with(' '));
+    is_key.then_some(key)
+}
+
+/// Whether a key line carries an inline value (so has no nested block).
+fn has_inline_value(line: &str) -> bool {
+    line.split_once(':')
+        .map(|(_, v)| {
+            let v = v.trim();
+            !v.is_empty() && !v.starts_with('#')
+        })
+        .unwrap_or(false)
+}
+
+/// Split header lines into entries for keys at `indent` spaces.
+fn parse_entries(lines: &[String], indent: usize) -> ParsedHeader {
+    let mut entries: Vec<Entry> = Vec::new();
+    let mut pending: Vec<String> = Vec::new();
+    let mut section: Option<String> = None;
+
+    for line in lines {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        if indent == 0 && is_section_comment(line) {
+            section = Some(trimmed.to_string());
+            continue;
+        }
+        if let Some(key) = key_at(line, indent) {
+            entries.push(Entry {
+                key: key.to_string(),
+                comments: std::mem::take(&mut pending),
+                lines: vec![line.clone()],
+                section: section.clone(),
+            });
+            continue;
+        }
+
+        let line_indent = line.len() - line.trim_start().len();
+        if trimmed.starts_with('#') && line_indent <= indent {
+            pending.push(line.clone());
+        } else if let Some(entry) = entries.last_mut() {
+            entry.lines.append(&mut pending);
+            entry.lines.push(line.clone());
+        } else {
+            pending.push(line.clone());
+        }
+    }
+
+    ParsedHeader { entries, trailing: pending }
+}
+
+/// Emit one entry, reordering and filling its nested fields.
+fn emit_entry(entry: &Entry, field: Option<&Field>, out: &mut Vec<String>, filled: &mut Vec<String>, missing: &mut Vec<String>) {
+    out.extend(entry.comments.iter().cloned());
+
+    let children = field.map(|f| f.children).unwrap_or_default();
+    if children.is_empty() || has_inline_value(&entry.lines[0]) {
+        out.extend(entry.lines.iter().cloned());
+        return;
+    }
+
+    out.push(entry.lines[0].clone());
+    let nested = parse_entries(&entry.lines[1..], 2);
+    let mut used = vec![false; nested.entries.len()];
+
+    for child in children {
+        match nested.entries.iter().position(|e| e.key == child.key) {
+            Some(idx) => {
+                used[idx] = true;
+                out.extend(nested.entries[idx].comments.iter().cloned());
+                out.extend(nested.entries[idx].lines.iter().cloned());
+            }
+            None => match child.default {
+                Some(default) => {
+                    out.push(format!("  {}: {}", child.key, default));
+                    filled.push(format!("{}.{}", entry.key, child.key));
+                }
+                None => missing.push(format!("{}.{}", entry.key, child.key)),
+            },
+        }
+    }
+    for (idx, e) in nested.entries.iter().enumerate() {
+        if !used[idx] {
+            out.extend(e.comments.iter().cloned());
+            out.extend(e.lines.iter().cloned());
+        }
+    }
+    out.extend(nested.trailing);
+}
+
+/// Emit a missing field with its default value, including nested defaults.
+///
+/// A nested field is only added when every nested field has a default.
+fn emit_default(field: &Field, out: &mut Vec<String>, filled: &mut Vec<String>, missing: &mut Vec<String>) {
+    if !field.children.is_empty() {
+        if field.children.iter().any(|c| c.default.is_none()) {
+            missing.push(field.key.to_string());
+            return;
+        }
+        out.push(format!("{}:", field.key));
+        filled.push(field.key.to_string());
+        for child in field.children {
+            out.push(format!("  {}: {}", child.key, child.default.unwrap_or("null")));
+        }
+        return;
+    }
+    match field.default {
+        Some(default) => {
+            out.push(format!("{}: {}", field.key, default));
+            filled.push(field.key.to_string());
+        }
+        None => missing.push(field.key.to_string()),
+    }
+}
+
+/// Split a document into its header lines (without line endings), the
+/// line ending in use, and the byte offset where the closing `---` starts.
+pub(crate) fn split_header(content: &str) -> Option<(Vec<String>, &'static str, usize)> {
+    let eol = if content.starts_with("---\r\n") {
+        "\r\n"
+    } else if content.starts_with("---\n") {
+        "\n"
+    } else {
+        return None;
+    };
+
+    let mut offset = 3 + eol.len();
+    let mut lines = Vec::new();
+    for raw in content[offset..].split_inclusive('\n') {
+        let line = raw.trim_end_matches('\n').trim_end_matches('\r');
+        if line == "---" {
+            return Some((lines, eol, offset));
+        }
+        lines.push(line.to_string());
+        offset += raw.len();
+    }
+    None
+}
+
+/// Whether every value in `before` is present and unchanged in `after`.
+fn preserves_values(before: &Value, after: &Value) -> bool {
+    match (before, after) {
+        (Value::Mapping(b), Value::Mapping(a)) => b
+            .iter()
+            .all(|(k, v)| a.get(k).is_some_and(|av| preserves_values(v, av))),
+        _ => before == after,
+    }
+}
+
+/// Normalize a document's header to `layout`.
+///
+/// Fails if the header cannot be parsed, or if rewriting it would change any
+/// value the header already had.
+pub(crate) fn normalize_content(content: &str, layout: &Layout) -> Result<Normalized, String> {
+    let (lines, eol, header_end) = split_header(content).ok_or("missing YAML frontmatter")?;
+    let before: Value = serde_yaml::from_str(&lines.join("\n")).map_err(|e| format!("invalid YAML: {}", e))?;
+
+    let parsed = parse_entries(&lines, 0);
+    let mut used = vec![false; parsed.entries.len()];
+    let mut out: Vec<String> = Vec::new();
+    let mut filled = Vec::new();
+    let mut missing = Vec::new();
+
+    for (i, section) in layout.sections.iter().enumerate() {
+        if i == layout.extras_before {
+            // Unknown keys, grouped under the section comment they appeared in
+            let mut last_section: Option<&str> = None;
+            for (idx, entry) in parsed.entries.iter().enumerate() {
+                if layout.sections.iter().any(|s| s.fields.iter().any(|f| f.key == entry.key)) {
+                    continue;
+                }
+                used[idx] = true;
+                let entry_section = entry
+                    .section
+                    .as_deref()
+                    .filter(|c| !layout.sections.iter().any(|s| s.comment == Some(*c)));
+                if last_section.is_none() || entry_section != last_section {
+                    out.push(String::new());
+                    out.extend(entry_section.map(str::to_string));
+                    last_section = entry_section.or(Some(""));
+                }
+                emit_entry(entry, None, &mut out, &mut filled, &mut missing);
+            }
+        }
+
+        if let Some(comment) = section.comment {
+            out.push(String::new());
+            out.push(comment.to_string());
+        }
+        for field in section.fields {
+            match parsed.entries.iter().position(|e| e.key == field.key) {
+                Some(idx) => {
+                    used[idx] = true;
+                    emit_entry(&parsed.entries[idx], Some(field), &mut out, &mut filled, &mut missing);
+                }
+                None => emit_default(field, &mut out, &mut filled, &mut missing),
+            }
+        }
+    }
+
+    // Duplicate keys of known fields are kept rather than dropped
+    for (idx, entry) in parsed.entries.iter().enumerate() {
+        if !used[idx] {
+            emit_entry(entry, None, &mut out, &mut filled, &mut missing);
+        }
+    }
+    out.extend(parsed.trailing);
+
+    let after: Value = serde_yaml::from_str(&out.join("\n")).map_err(|e| format!("normalized header is invalid YAML: {}", e))?;
+    if !preserves_values(&before, &after) {
+        return Err("normalizing would change existing header values".to_string());
+    }
+
+    let mut normalized = String::from("---");
+    normalized.push_str(eol);
+    for line in &out {
+        normalized.push_str(line);
+        normalized.push_str(eol);
+    }
+    normalized.push_str(&content[header_end..]);
+
+    Ok(Normalized {
+        content: normalized,
+        filled,
+        missing,
+    })
+}
+
+/// Run the `header normalize` command.
+///
+/// With `check`, nothing is written and the command fails if any document
+/// is not already normalized.
+pub fn normalize(repo_root_arg: Option<String>, file_path: Option<String>, check: bool) -> Result<(), FrontmatterError> {
+    let repo_root = match repo_root_arg {
+        Some(path) => PathBuf::from(path),
+        None => find_repo_root()?.ok_or(FrontmatterError::NoSpecsDir)?,
+    };
+    let layout = &LAYOUT_V2_1;
+
+    println!("=== XONAIX HEADER NORMALIZATION ===");
+    println!("Repository: {}", repo_root.display());
+    println!("Schema Version: {}", layout.schema_version);
+    println!("Mode: {}", if check { "check" } else { "write" });
+    println!();
+
+    let mut changed = 0u32;
+    let mut unchanged = 0u32;
+    let mut failed = 0u32;
+
+    for path in collect_documents(&repo_root, file_path)? {
+        let content = fs::read_to_string(&path)?;
+        let normalized = match normalize_content(&content, layout) {
+            Ok(n) => n,
+            Err(reason) => {
+                println!("FAIL: {}: {}", path.display(), reason);
+                failed += 1;
+                continue;
+            }
+        };
+
+        for key in &normalized.missing {
+            println!("WARN: {}: required field {} is missing and has no default", path.display(), key);
+        }
+
+        if normalized.content == content {
+            unchanged += 1;
+            continue;
+        }
+
+        changed += 1;
+        let verb = if check { "NOT NORMALIZED" } else { "NORMALIZED" };
+        if normalized.filled.is_empty() {
+            println!("{}: {}", verb, path.display());
+        } else {
+            println!("{}: {} (filled: {})", verb, path.display(), normalized.filled.join(", "));
+        }
+        if !check {
+            fs::write(&path, &normalized.content)?;
+        }
+    }
+
+    println!();
+    println!("=== NORMALIZATION COMPLETE ===");
+    println!("{}: {}", if check { "Would change" } else { "Normalized" }, changed);
+    println!("Unchanged: {}", unchanged);
+    println!("Failed: {}", failed);
+
+    if failed > 0 {
+        Err(FrontmatterError::NormalizeFailed(failed))
+    } else if check && changed > 0 {
+        Err(FrontmatterError::NotNormalized(changed))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHUFFLED: &str = "---\nstatus: \"draft\"\ncustom: 1\nschema: \"x\"\nintegrity:\n  content_hash: null\n---\nbody\n";
+
+    #[test]
+    fn normalize_reorders_and_fills_defaults() {
+        let normalized = normalize_content(SHUFFLED, &LAYOUT_V2_1);
+        assert!(normalized.as_ref().is_ok_and(|n| n.content.starts_with(
+            "---\nschema: \"x\"\n\n# --- Identity ---\n\n# --- Version ---\nbaseline: null\nstatus: \"draft\"\n"
+        )));
+        assert!(normalized.as_ref().is_ok_and(|n| n.content.contains(
+            "integrity:\n  hash_alg: null\n  content_hash: null\n  signature: null\n  signed_by: null\n  signed_at: null\n"
+        )));
+        assert!(normalized.as_ref().is_ok_and(|n| n.content.contains("custom: 1\n\n# --- Lifecycle ---\n")));
+        assert!(normalized.as_ref().is_ok_and(|n| n.content.ends_with("\n---\nbody\n")));
+        assert!(normalized.as_ref().is_ok_and(|n| n.filled.contains(&"integrity.hash_alg".to_string())));
+        assert!(normalized.as_ref().is_ok_and(|n| n.missing.contains(&"schema_version".to_string())));
+        assert!(normalized.as_ref().is_ok_and(|n| n.missing.contains(&"authority".to_string())));
+    }
+
+    #[test]
+    fn normalize_is_idempotent() {
+        let once = normalize_content(SHUFFLED, &LAYOUT_V2_1).map(|n| n.content).unwrap_or_default();
+        let twice = normalize_content(&once, &LAYOUT_V2_1);
+        assert!(twice.is_ok_and(|n| n.content == once && n.filled.is_empty()));
+    }
+
+    #[test]
+    fn normalize_keeps_crlf_line_endings() {
+        let crlf = SHUFFLED.replace('\n', "\r\n");
+        let normalized = normalize_content(&crlf, &LAYOUT_V2_1).map(|n| n.content).unwrap_or_default();
+        assert!(!normalized.is_empty());
+        assert!(!normalized.replace("\r\n", "").contains('\n'));
+    }
+
+    #[test]
+    fn normalize_requires_frontmatter() {
+        assert!(normalize_content("body\n", &LAYOUT_V2_1).is_err());
+        assert!(normalize_content("---\nstatus: [\n---\nbody\n", &LAYOUT_V2_1).is_err());
+    }
+
+    #[test]
+    fn split_header_stops_at_the_exact_delimiter() {
+        let split = split_header("---\r\ntitle: x\r\n----\r\n---\r\nbody");
+        assert_eq!(split, Some((vec!["title: x".to_string(), "----".to_string()], "\r\n", 21)));
+    }
+}
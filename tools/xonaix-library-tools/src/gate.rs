@@ -0,0 +1,399 @@
+//! Seal gate module.
+//!
+//! Answers "are these specs sealed?" for code generators, as required of
+//! Forge by MULTI_PARTY_GOVERNANCE_SPEC §7.3. The answer is printed as JSON
+//! and, with `--audit-log`, appended to an audit log, so every generation
+//! run records exactly which spec versions and content hashes it relied on.
+//! The log lives with the caller, never in the tracked specs tree. Errors
+//! while checking close the gate and are recorded in the answer, so the JSON
+//! and the audit record are written on every run.
+
+use chrono::Utc;
+use serde::Serialize;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+use crate::bom;
+use crate::canonical;
+use crate::common::{find_repo_root, relative_path};
+use crate::engagement;
+use crate::keyring;
+use crate::manifest;
+use crate::unit;
+
+/// Errors during a gate query.
+#[derive(Debug, Error)]
+pub enum GateError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Cannot find specs/ directory")]
+    NoSpecsDir,
+
+    #[error("No unit_ids given")]
+    NoUnits,
+
+    #[error("Gate closed: {0} of {1} unit(s) not sealed")]
+    Blocked(usize, usize),
+
+    #[error("Gate closed: pinned BOM {0} does not verify")]
+    BomNotVerified(String),
+
+    #[error("Gate closed: {0} error(s) while checking")]
+    CheckFailed(usize),
+}
+
+/// Gate answer, printed as JSON and recorded in the audit log.
+#[derive(Debug, Serialize)]
+struct GateResult {
+    allowed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bom: Option<GateBom>,
+    checked_at: String,
+    /// Errors that kept the gate from checking the request.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    errors: Vec<String>,
+    generator: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    requested_by: Option<String>,
+    units: Vec<GateUnit>,
+}
+
+/// State of the pinned Library BOM.
+#[derive(Debug, Serialize)]
+struct GateBom {
+    bom_hash: Option<String>,
+    library_version: String,
+    problems: Vec<String>,
+    signature_valid: bool,
+}
+
+/// State of one requested unit.
+#[derive(Debug, Serialize)]
+struct GateUnit {
+    documents: Vec<GateDocument>,
+    problems: Vec<String>,
+    /// `sealed`, `blocked` or `unknown`.
+    status: String,
+    unit_id: String,
+    /// Version from the unit's UNIT.json.
+    version: Option<String>,
+}
+
+/// State of one document in a requested unit.
+#[derive(Debug, Serialize)]
+struct GateDocument {
+    content_hash: Option<String>,
+    path: String,
+    signature_valid: bool,
+    signed_by: Option<String>,
+    status: String,
+}
+
+/// Check the pinned BOM's seal and signer.
+fn check_bom(repo_root: &Path, library_version: &str, keyring_path: Option<&Path>) -> (GateBom, Option<bom::LibraryBom>) {
+    let mut gate_bom = GateBom {
+        bom_hash: None,
+        library_version: library_version.to_string(),
+        problems: Vec::new(),
+        signature_valid: false,
+    };
+
+    if let Err(e) = bom::validate_library_version(library_version) {
+        gate_bom.problems.push(e.to_string());
+        return (gate_bom, None);
+    }
+
+    let bom_file = bom::bom_path(repo_root, library_version);
+    if !bom_file.exists() {
+        gate_bom.problems.push(format!("BOM not found: {}", relative_path(&bom_file, repo_root)));
+        return (gate_bom, None);
+    }
+    let library_bom = match bom::load_bom(&bom_file) {
+        Ok(b) => b,
+        Err(e) => {
+            gate_bom.problems.push(e.to_string());
+            return (gate_bom, None);
+        }
+    };
+    match bom::hash_file(&bom_file) {
+        Ok(hash) => gate_bom.bom_hash = Some(hash),
+        Err(e) => gate_bom.problems.push(e.to_string()),
+    }
+
+    match bom::check_seal(&bom_file, &bom::seal_path(repo_root, library_version), &library_bom) {
+        Ok(seal) => match bom::check_signer(repo_root, keyring_path, &seal) {
+            Ok(_) => gate_bom.signature_valid = true,
+            Err(reason) => gate_bom.problems.push(reason),
+        },
+        Err(reason) => gate_bom.problems.push(reason),
+    }
+
+    (gate_bom, Some(library_bom))
+}
+
+/// Check one unit: every document sealed with a valid signature, and, when a
+/// BOM is pinned, the unit unchanged since that release.
+fn check_unit(
+    repo_root: &Path,
+    registry: &unit::UnitRegistry,
+    unit_id: &str,
+    keyring: Result<&keyring::Keyring, &str>,
+    pinned: Option<&bom::LibraryBom>,
+) -> GateUnit {
+    let mut gate_unit = GateUnit {
+        documents: Vec::new(),
+        problems: Vec::new(),
+        status: "unknown".to_string(),
+        unit_id: unit_id.to_string(),
+        version: None,
+    };
+
+    let entry = match registry.units.get(unit_id) {
+        Some(e) => e,
+        None => {
+            gate_unit.problems.push("not in UNIT_REGISTRY.json".to_string());
+            return gate_unit;
+        }
+    };
+
+    match unit::load_unit_json(&repo_root.join(&entry.path).join("UNIT.json")) {
+        Ok(u) => gate_unit.version = Some(u.version),
+        Err(e) => gate_unit.problems.push(format!("UNIT.json: {}", e)),
+    }
+
+    if let Err(reason) = keyring {
+        gate_unit.problems.push(format!("signer keyring: {}", reason));
+    }
+    let documents = match engagement::unit_documents(repo_root, registry, unit_id, keyring.ok(), &[]) {
+        Ok(documents) => documents.unwrap_or_default(),
+        Err(e) => {
+            gate_unit.problems.push(e.to_string());
+            Vec::new()
+        }
+    };
+    if documents.is_empty() {
+        gate_unit.problems.push("unit has no documents".to_string());
+    }
+
+    for doc in documents {
+        let rel = relative_path(&doc.path, repo_root);
+        gate_unit.problems.extend(doc.problems.iter().map(|p| format!("{}: {}", rel, p)));
+
+        let gate_doc = GateDocument {
+            content_hash: doc.content_hash,
+            path: rel.clone(),
+            signature_valid: doc.signed_by.is_some(),
+            signed_by: doc.signed_by,
+            status: doc.status,
+        };
+        gate_unit.documents.push(gate_doc);
+    }
+
+    if let Some(library_bom) = pinned {
+        match library_bom.units.iter().find(|u| u.unit_id == unit_id) {
+            None => gate_unit.problems.push(format!("not in BOM {}", library_bom.library_version)),
+            Some(bom_unit) => {
+                if gate_unit.version.as_deref() != Some(bom_unit.version.as_str()) {
+                    gate_unit.problems.push(format!(
+                        "version differs from BOM {} ({})",
+                        library_bom.library_version, bom_unit.version
+                    ));
+                }
+                gate_unit.problems.extend(check_pinned_manifest(repo_root, unit_id, &bom_unit.manifest, &bom_unit.manifest_hash));
+            }
+        }
+    }
+
+    gate_unit.status = if gate_unit.problems.is_empty() { "sealed" } else { "blocked" }.to_string();
+    gate_unit
+}
+
+/// Problems with a unit's manifest pinned in a BOM: missing, changed, or
+/// no longer matching the unit's files.
+fn check_pinned_manifest(repo_root: &Path, unit_id: &str, manifest: &str, manifest_hash: &str) -> Vec<String> {
+    let manifest_file = match manifest::repo_path(repo_root, manifest) {
+        Ok(path) => path,
+        Err(reason) => return vec![reason],
+    };
+    if !manifest_file.exists() {
+        return vec![format!("manifest not found: {}", manifest)];
+    }
+    match bom::hash_file(&manifest_file) {
+        Ok(hash) if hash.eq_ignore_ascii_case(manifest_hash) => {}
+        Ok(_) => return vec![format!("manifest hash mismatch: {}", manifest)],
+        Err(e) => return vec![format!("{}: {}", manifest, e)],
+    }
+    bom::diff_unit_files(repo_root, unit_id, &manifest_file).unwrap_or_else(|e| vec![e.to_string()])
+}
+
+/// Append a result to the audit log as one canonical JSON line.
+fn append_audit(path: &Path, line: &str) -> Result<(), GateError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", line)?;
+    Ok(())
+}
+
+/// Run the `gate` command.
+///
+/// Prints the gate answer as JSON to stdout (or `output`) and records it in
+/// `audit_log` when one is given. Fails when any requested unit is not sealed, the pinned
+/// BOM does not verify, or the check itself failed.
+pub fn run(
+    repo_root_arg: Option<String>,
+    unit_ids: Vec<String>,
+    library_version: Option<String>,
+    keyring_path: Option<String>,
+    requested_by: Option<String>,
+    audit_log: Option<String>,
+    output: Option<String>,
+) -> Result<(), GateError> {
+    let repo_root = match repo_root_arg {
+        Some(path) => PathBuf::from(path),
+        None => find_repo_root()?.ok_or(GateError::NoSpecsDir)?,
+    };
+    if unit_ids.is_empty() {
+        return Err(GateError::NoUnits);
+    }
+
+    let mut errors = Vec::new();
+    let keyring_path = keyring_path.map(PathBuf::from);
+    let keyring = keyring::load_keyring(&repo_root, keyring_path.as_deref()).map_err(|e| e.to_string());
+
+    let (gate_bom, pinned) = match &library_version {
+        Some(v) => {
+            let (gate_bom, pinned) = check_bom(&repo_root, v, keyring_path.as_deref());
+            (Some(gate_bom), pinned)
+        }
+        None => (None, None),
+    };
+
+    let mut units = Vec::new();
+    match unit::load_registry(&repo_root) {
+        Ok(registry) => {
+            for unit_id in &unit_ids {
+                units.push(check_unit(
+                    &repo_root,
+                    &registry,
+                    unit_id,
+                    keyring.as_ref().map_err(String::as_str),
+                    pinned.as_ref(),
+                ));
+            }
+        }
+        Err(e) => errors.push(e.to_string()),
+    }
+
+    let blocked = units.iter().filter(|u| u.status != "sealed").count();
+    let bom_ok = gate_bom.as_ref().is_none_or(|b| b.problems.is_empty());
+    let result = GateResult {
+        allowed: errors.is_empty() && blocked == 0 && bom_ok,
+        bom: gate_bom,
+        checked_at: Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+        errors,
+        generator: format!("xonaix-library-tools {}", env!("CARGO_PKG_VERSION")),
+        requested_by,
+        units,
+    };
+
+    let json = canonical::to_canonical_string(&result)?;
+    let printed = match output {
+        Some(path) => fs::write(&path, format!("{}\n", json)),
+        None => {
+            println!("{}", json);
+            Ok(())
+        }
+    };
+
+    if let Some(audit_path) = audit_log {
+        append_audit(Path::new(&audit_path), &json)?;
+    }
+    printed?;
+
+    if !result.errors.is_empty() {
+        Err(GateError::CheckFailed(result.errors.len()))
+    } else if blocked > 0 {
+        Err(GateError::Blocked(blocked, result.units.len()))
+    } else if !bom_ok {
+        Err(GateError::BomNotVerified(library_version.unwrap_or_default()))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_repo(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("xlib-gate-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let _ = fs::create_dir_all(root.join("specs"));
+        root
+    }
+
+    /// Regression: a load error used to skip both the JSON answer and the audit record.
+    #[test]
+    fn errors_are_answered_and_audited() {
+        let root = temp_repo("errors");
+        let output = root.join("answer.json");
+        let audit_log = root.join("audit").join("gate.jsonl");
+        let result = run(
+            Some(root.display().to_string()),
+            vec!["library/standards/rust".to_string()],
+            None,
+            None,
+            None,
+            Some(audit_log.display().to_string()),
+            Some(output.display().to_string()),
+        );
+        assert!(matches!(result, Err(GateError::CheckFailed(1))));
+
+        let answer = fs::read_to_string(&output).unwrap_or_default();
+        let audit = fs::read_to_string(&audit_log).unwrap_or_default();
+        assert!(answer.starts_with(r#"{"allowed":false,"#) && answer.contains(r#""errors":["#));
+        assert_eq!(audit, answer);
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn nothing_is_written_to_the_specs_tree_without_audit_log() {
+        let root = temp_repo("no-audit");
+        let output = root.join("answer.json");
+        let result = run(
+            Some(root.display().to_string()),
+            vec!["library/standards/rust".to_string()],
+            None,
+            None,
+            None,
+            None,
+            Some(output.display().to_string()),
+        );
+        assert!(matches!(result, Err(GateError::CheckFailed(1))));
+
+        let specs: Vec<_> = fs::read_dir(root.join("specs")).map(|d| d.flatten().collect()).unwrap_or_default();
+        assert!(output.exists() && specs.is_empty());
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn unreadable_bom_is_a_problem() {
+        let root = temp_repo("bom");
+        let bom_file = bom::bom_path(&root, "LIB-1.0.0");
+        let _ = bom_file.parent().map(fs::create_dir_all);
+        assert!(fs::write(&bom_file, "not json").is_ok());
+
+        let (gate_bom, pinned) = check_bom(&root, "LIB-1.0.0", None);
+        assert!(pinned.is_none());
+        assert_eq!(gate_bom.problems.len(), 1);
+        let _ = fs::remove_dir_all(&root);
+    }
+}
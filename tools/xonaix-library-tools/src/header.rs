@@ -2,12 +2,22 @@
 //!
 //! Validates document headers against the v2.1 schema specification.
 
+use chrono::{DateTime, Utc};
 use serde::Deserialize;
 use std::fs;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 use walkdir::WalkDir;
 
+use crate::anchor::{self, ZeropointAnchor};
+use crate::approval::{self, Approval, ApprovalRequirements};
+use crate::common::{extract_frontmatter, find_repo_root, get_yaml_string, should_exclude};
+use crate::keyring::{self, Keyring};
+use crate::seal;
+use crate::sign;
+use crate::unit::{self, UnitRegistry};
+use crate::version;
+
 /// Errors during header validation.
 #[derive(Debug, Error)]
 pub enum HeaderError {
@@ -67,19 +77,16 @@ struct DocumentHeader {
     status: Option<String>,
     trust_class: Option<serde_yaml::Value>,
     classification: Option<String>,
-    compliance: Option<Vec<String>>,
     owner: Option<String>,
-    approved_by: Option<serde_yaml::Value>,
     authority_tier: Option<String>,
     authority: Option<Authority>,
-    depends_on: Option<Vec<serde_yaml::Value>>,
-    supersedes: Option<serde_yaml::Value>,
-    superseded_by: Option<serde_yaml::Value>,
-    implements: Option<Vec<serde_yaml::Value>>,
     integrity: Option<Integrity>,
     constitutional_conformance: Option<ConstitutionalConformance>,
-    created: Option<String>,
-    last_updated: Option<String>,
+    zeropoint_anchor: Option<ZeropointAnchor>,
+    approvals: Option<Vec<Approval>>,
+    approval_requirements: Option<ApprovalRequirements>,
+    created: Option<serde_yaml::Value>,
+    last_updated: Option<serde_yaml::Value>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -87,7 +94,6 @@ struct Authority {
     repo: Option<String>,
     #[serde(rename = "ref")]
     ref_: Option<String>,
-    version: Option<serde_yaml::Value>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -95,19 +101,22 @@ struct Integrity {
     hash_alg: Option<serde_yaml::Value>,
     content_hash: Option<serde_yaml::Value>,
     signature: Option<serde_yaml::Value>,
-    signed_by: Option<serde_yaml::Value>,
     signed_at: Option<serde_yaml::Value>,
 }
 
 #[derive(Debug, Deserialize)]
 struct ConstitutionalConformance {
-    constitution_version: Option<serde_yaml::Value>,
-    constitution_hash: Option<serde_yaml::Value>,
-    zero_point_version: Option<serde_yaml::Value>,
-    zero_point_hash: Option<serde_yaml::Value>,
-    deviations: Option<Vec<String>>,
     last_verified: Option<serde_yaml::Value>,
-    verified_by: Option<serde_yaml::Value>,
+}
+
+/// State shared by every header validated in one run.
+struct ValidationContext {
+    /// Canonicalized repository root, for checking `path`.
+    repo_root: PathBuf,
+    /// Trusted keys for document and approval signatures, if the keyring could be loaded.
+    keyring: Option<Keyring>,
+    /// Unit registry, if it could be loaded.
+    registry: Option<UnitRegistry>,
 }
 
 /// Validation result for a single file.
@@ -116,85 +125,285 @@ struct ValidationResult {
     warnings: Vec<String>,
 }
 
-/// Find repository root.
-fn find_repo_root() -> Result<PathBuf, HeaderError> {
-    let mut current = std::env::current_dir()?;
 
-    if current.join("specs").exists() {
-        return Ok(current);
+/// Check if a serde_yaml::Value is null or represents null.
+fn is_yaml_null(v: &Option<serde_yaml::Value>) -> bool {
+    matches!(v, None | Some(serde_yaml::Value::Null))
+}
+
+/// Parse a header timestamp: RFC 3339 in UTC, with a `Z` suffix.
+pub(crate) fn parse_utc_timestamp(value: &str) -> Result<DateTime<Utc>, String> {
+    let parsed = DateTime::parse_from_rfc3339(value).map_err(|_| format!("'{}' is not an RFC 3339 timestamp", value))?;
+    if !value.ends_with('Z') {
+        return Err(format!("'{}' is not UTC (must end in Z)", value));
     }
+    Ok(parsed.with_timezone(&Utc))
+}
 
-    while let Some(parent) = current.parent() {
-        if parent.join("specs").exists() {
-            return Ok(parent.to_path_buf());
+/// Validate lifecycle, signing and conformance timestamps.
+///
+/// Each must be a UTC RFC 3339 timestamp that is not in the future, with
+/// `created <= last_updated` and `created <= signed_at`.
+fn verify_timestamps(header: &DocumentHeader, result: &mut ValidationResult) {
+    let now = Utc::now();
+    let integrity = header.integrity.as_ref();
+    let conformance = header.constitutional_conformance.as_ref();
+
+    let mut check = |field: &str, value: Option<&serde_yaml::Value>| -> Option<DateTime<Utc>> {
+        let value = match value {
+            None | Some(serde_yaml::Value::Null) => return None,
+            Some(serde_yaml::Value::String(s)) => s,
+            Some(_) => {
+                result.errors.push(format!("{} must be a quoted timestamp string", field));
+                return None;
+            }
+        };
+        match parse_utc_timestamp(value) {
+            Ok(ts) if ts > now => {
+                result.errors.push(format!("{} is in the future: {}", field, value));
+                Some(ts)
+            }
+            Ok(ts) => Some(ts),
+            Err(e) => {
+                result.errors.push(format!("Invalid {}: {}", field, e));
+                None
+            }
         }
-        current = parent.to_path_buf();
-    }
+    };
 
-    Err(HeaderError::NoSpecsDir)
+    let created = check("created", header.created.as_ref());
+    let last_updated = check("last_updated", header.last_updated.as_ref());
+    let signed_at = check("integrity.signed_at", integrity.and_then(|i| i.signed_at.as_ref()));
+    let _ = check("constitutional_conformance.last_verified", conformance.and_then(|c| c.last_verified.as_ref()));
+
+    if let (Some(created), Some(last_updated)) = (created, last_updated)
+        && last_updated < created
+    {
+        result.errors.push(format!(
+            "last_updated ({}) is before created ({})",
+            last_updated.format("%Y-%m-%dT%H:%M:%SZ"),
+            created.format("%Y-%m-%dT%H:%M:%SZ")
+        ));
+    }
+    if let (Some(created), Some(signed_at)) = (created, signed_at)
+        && signed_at < created
+    {
+        result.errors.push(format!(
+            "integrity.signed_at ({}) is before created ({})",
+            signed_at.format("%Y-%m-%dT%H:%M:%SZ"),
+            created.format("%Y-%m-%dT%H:%M:%SZ")
+        ));
+    }
 }
 
+/// Recompute the body hash and compare it with `integrity.content_hash`.
+fn verify_content_hash(integrity: &Integrity, content: &str, result: &mut ValidationResult) {
+    match get_yaml_string(&integrity.hash_alg) {
+        Some(seal::CONTENT_HASH_ALG) => {}
+        Some(alg) => {
+            result.errors.push(format!("Unsupported integrity.hash_alg: {} (expected {})", alg, seal::CONTENT_HASH_ALG));
+            return;
+        }
+        None => {
+            result.errors.push(format!("content_hash is set but hash_alg is not {}", seal::CONTENT_HASH_ALG));
+            return;
+        }
+    }
 
-/// Check if path should be excluded from validation.
-fn should_exclude(path: &Path) -> bool {
-    let path_str = path.to_string_lossy();
+    let stored = match get_yaml_string(&integrity.content_hash) {
+        Some(h) => h,
+        None => {
+            result.errors.push("integrity.content_hash must be a string".to_string());
+            return;
+        }
+    };
 
-    if path_str.contains("_deprecated/") || path_str.contains("_deprecated\\") {
-        return true;
+    if let Some(computed) = seal::compute_content_hash(content)
+        && !stored.eq_ignore_ascii_case(&computed)
+    {
+        result.errors.push(format!(
+            "content tampered: stored content_hash does not match computed {} body hash ({}...)",
+            seal::CONTENT_HASH_ALG,
+            &computed[..16]
+        ));
     }
-    if path_str.contains("_reference/") || path_str.contains("_reference\\") {
-        return true;
+}
+
+/// Check a populated `zeropoint_anchor` section against the document's content hash.
+///
+/// The anchor is checked against the hash computed from the document body,
+/// never the stored `content_hash`, and against the block in the ledger.
+fn verify_zeropoint_anchor(header: &DocumentHeader, content: &str, ctx: &ValidationContext, result: &mut ValidationResult) {
+    let anchor = match &header.zeropoint_anchor {
+        Some(anchor) if anchor.is_populated() => anchor,
+        _ => return,
+    };
+
+    let status = header.status.as_deref().unwrap_or_default();
+    if !matches!(status, "sealed" | "deprecated" | "superseded") {
+        result.errors.push(format!("zeropoint_anchor is set but status is '{}' (only sealed documents are anchored)", status));
     }
-    if path_str.contains(".git/") || path_str.contains(".git\\") {
-        return true;
+
+    let content_hash = match seal::compute_content_hash(content) {
+        Some(h) => h,
+        None => return,
+    };
+
+    match anchor::verify_document_anchor(&ctx.repo_root, anchor, &content_hash) {
+        Ok(problems) => result.errors.extend(problems),
+        Err(e) => result.errors.push(format!("zeropoint_anchor: {}", e)),
     }
-    if path_str.contains("/target/") || path_str.contains("\target\\") {
-        return true;
+}
+
+/// Verify the document signature against the signer keyring.
+///
+/// A signature that cannot be checked because the keyring is unavailable is
+/// an error, as is one that does not verify or whose signer is not
+/// authorized for the document's tier and trust class.
+fn verify_signature(path: &Path, content: &str, ctx: &ValidationContext, result: &mut ValidationResult) {
+    let keyring = match &ctx.keyring {
+        Some(k) => k,
+        None => {
+            result.errors.push("signature not verified: signer keyring could not be loaded".to_string());
+            return;
+        }
+    };
+
+    match sign::verify_document_signature(path, content, keyring) {
+        Ok(Some(verified)) => {
+            if let Some(revoked_at) = verified.revoked_at {
+                result.warnings.push(format!(
+                    "signing key {} was revoked at {} after signing, re-sign required",
+                    verified.key_id,
+                    revoked_at.to_rfc3339()
+                ));
+            }
+        }
+        Ok(None) => {}
+        Err(reason) => result.errors.push(format!("signature invalid: {}", reason)),
     }
-    if path_str.contains("manifests/") || path_str.contains("manifests\\") {
-        return true;
+}
+
+/// Evaluate the `approvals` chain and `approval_requirements`.
+///
+/// Unmet requirements are errors for sealed documents and warnings while
+/// the document is still being approved.
+fn verify_approvals(header: &DocumentHeader, content: &str, ctx: &ValidationContext, result: &mut ValidationResult) {
+    if header.approvals.is_none() && header.approval_requirements.is_none() {
+        return;
     }
-    if path_str.contains("_roadmap/") || path_str.contains("_roadmap\\") {
-        return true;
+
+    let stored = header.integrity.as_ref().and_then(|i| get_yaml_string(&i.content_hash));
+    let content_hash = match stored.map(str::to_string).or_else(|| seal::compute_content_hash(content)) {
+        Some(h) => h,
+        None => return,
+    };
+
+    let approvals = header.approvals.as_deref().unwrap_or_default();
+    let eval = approval::evaluate(approvals, header.approval_requirements.as_ref(), &content_hash, ctx.keyring.as_ref());
+
+    result.errors.extend(eval.errors);
+
+    let sealed = matches!(header.status.as_deref(), Some("sealed" | "deprecated" | "superseded"));
+    for unmet in eval.unmet {
+        let message = format!("approval requirements not met: {}", unmet);
+        if sealed {
+            result.errors.push(message);
+        } else {
+            result.warnings.push(message);
+        }
     }
+}
 
-    false
+/// Repo-relative, forward-slash path of a document, if it is inside the repository.
+fn repo_relative(path: &Path, ctx: &ValidationContext) -> Option<String> {
+    fs::canonicalize(path)
+        .ok()
+        .and_then(|p| p.strip_prefix(&ctx.repo_root).ok().map(|r| r.to_string_lossy().replace('\\', "/")))
 }
 
-/// Extract YAML frontmatter from markdown content.
-fn extract_frontmatter(content: &str) -> Option<&str> {
-    if !content.starts_with("---") {
-        return None;
+/// Validate `version` and `baseline` (contract §5).
+///
+/// `version` must be `{PREFIX}-MAJOR.MINOR.PATCH` with a domain prefix, and
+/// the prefix the document's location requires; `baseline` is `XBASE-X.Y.Z`
+/// or null.
+fn verify_versions(path: &Path, header: &DocumentHeader, ctx: &ValidationContext, result: &mut ValidationResult) {
+    match header.version.as_deref().map(version::parse) {
+        Some(Ok(v)) => {
+            if !version::DOMAIN_PREFIXES.contains(&v.prefix.as_str()) {
+                result.errors.push(format!("Invalid version prefix: {}", v));
+            } else if let Some(expected) = repo_relative(path, ctx).as_deref().and_then(version::expected_prefix)
+                && v.prefix != expected
+            {
+                result.errors.push(format!("version {} should use the {}- prefix for this location", v, expected));
+            }
+        }
+        Some(Err(e)) => result.errors.push(format!("Invalid version: {}", e)),
+        None => result.errors.push("Missing version".to_string()),
     }
 
-    let rest = &content[3..];
-    if let Some(end_idx) = rest.find("\n---") {
-        Some(&rest[..end_idx])
-    } else {
-        None
+    match &header.baseline {
+        None | Some(serde_yaml::Value::Null) => {}
+        Some(serde_yaml::Value::String(b)) => match version::parse(b) {
+            Ok(v) if v.prefix == version::BASELINE_PREFIX => {}
+            Ok(v) => result.errors.push(format!("Invalid baseline: {} must use the {}- prefix", v, version::BASELINE_PREFIX)),
+            Err(e) => result.errors.push(format!("Invalid baseline: {}", e)),
+        },
+        Some(_) => result.errors.push("Invalid baseline: must be a string or null".to_string()),
     }
 }
 
-/// Check if a serde_yaml::Value is null or represents null.
-fn is_yaml_null(v: &Option<serde_yaml::Value>) -> bool {
-    match v {
-        None => true,
-        Some(serde_yaml::Value::Null) => true,
-        _ => false,
+/// Check `path`, `unit_id`, `version` and `owner` against the file's location,
+/// its sibling UNIT.json and UNIT_REGISTRY.json (contract §12).
+///
+/// Governance contracts under `specs/_governance/` are not library units and
+/// are not looked up in the registry.
+fn verify_consistency(path: &Path, header: &DocumentHeader, ctx: &ValidationContext, result: &mut ValidationResult) {
+    let rel = repo_relative(path, ctx);
+
+    if let (Some(declared), Some(actual)) = (&header.path, &rel)
+        && declared != actual
+    {
+        result.errors.push(format!("path is '{}' but the file is at '{}'", declared, actual));
+    }
+
+    let unit_json = path.parent().map(|p| p.join("UNIT.json"));
+    if let Some(unit_json) = unit_json.filter(|p| p.exists()) {
+        match unit::load_unit_json(&unit_json) {
+            Ok(u) => {
+                for (field, declared, expected) in [
+                    ("unit_id", &header.unit_id, &u.unit_id),
+                    ("version", &header.version, &u.version),
+                    ("owner", &header.owner, &u.owner),
+                ] {
+                    if let Some(declared) = declared
+                        && declared != expected
+                    {
+                        result.errors.push(format!("{} is '{}' but UNIT.json has '{}'", field, declared, expected));
+                    }
+                }
+            }
+            Err(e) => result.errors.push(format!("Cannot read UNIT.json: {}", e)),
+        }
     }
-}
 
-/// Get string value from serde_yaml::Value if it's a string.
-fn get_yaml_string(v: &Option<serde_yaml::Value>) -> Option<&str> {
-    match v {
-        Some(serde_yaml::Value::String(s)) => Some(s.as_str()),
-        _ => None,
+    let is_governance = rel.as_deref().is_some_and(|r| r.starts_with("specs/_governance/"));
+    if let Some(unit_id) = &header.unit_id
+        && !is_governance
+    {
+        match &ctx.registry {
+            Some(registry) if !registry.units.contains_key(unit_id) => {
+                result.errors.push(format!("unit_id '{}' is not in UNIT_REGISTRY.json", unit_id));
+            }
+            Some(_) => {}
+            None => result.errors.push("Cannot check unit_id: UNIT_REGISTRY.json could not be loaded".to_string()),
+        }
     }
 }
 
 
 /// Validate a single document header.
-fn validate_header(_path: &Path, content: &str) -> ValidationResult {
+fn validate_header(path: &Path, content: &str, ctx: &ValidationContext) -> ValidationResult {
     let mut result = ValidationResult {
         errors: Vec::new(),
         warnings: Vec::new(),
@@ -225,7 +434,7 @@ fn validate_header(_path: &Path, content: &str) -> ValidationResult {
 
     match header.schema_version.as_deref() {
         Some("2.0") => {
-            result.warnings.push("schema_version is 2.0, should migrate to 2.1".to_string());
+            result.warnings.push("schema_version is 2.0, should migrate to 2.1 (header migrate --to 2.1)".to_string());
         }
         Some("2.1") => {}
         Some(v) => {
@@ -265,22 +474,7 @@ fn validate_header(_path: &Path, content: &str) -> ValidationResult {
     }
 
     // Version validation
-    if let Some(version) = &header.version {
-        let valid_prefix = version.starts_with("XZERO-") ||
-            version.starts_with("XCORT-") ||
-            version.starts_with("XCODE-") ||
-            version.starts_with("XNEX-") ||
-            version.starts_with("XBLADE-") ||
-            version.starts_with("XINFRA-") ||
-            version.starts_with("XLIB-") ||
-            version.starts_with("XGOV-") ||
-            version.starts_with("XUX-");
-        if !valid_prefix {
-            result.errors.push(format!("Invalid version prefix: {}", version));
-        }
-    } else {
-        result.errors.push("Missing version".to_string());
-    }
+    verify_versions(path, &header, ctx, &mut result);
 
 
     // Status validation
@@ -314,17 +508,38 @@ fn validate_header(_path: &Path, content: &str) -> ValidationResult {
                 result.errors.push(format!("status is '{}' but signature is missing", status));
             }
         }
+
+        // Verify stored content hash against the document body
+        let integrity_required = status == "approved" || status == "sealed"
+            || status == "deprecated" || status == "superseded";
+        if integrity_required
+            && has_content_hash
+            && let Some(integrity) = &header.integrity
+        {
+            verify_content_hash(integrity, content, &mut result);
+        }
+
+        if has_signature {
+            verify_signature(path, content, ctx, &mut result);
+        }
     } else {
         result.errors.push("Missing status".to_string());
     }
 
+    // ZeroPoint anchor validation
+    verify_zeropoint_anchor(&header, content, ctx, &mut result);
+
+    // Multi-party approval validation
+    verify_approvals(&header, content, ctx, &mut result);
+
+    // Consistency with file location, UNIT.json and the unit registry
+    verify_consistency(path, &header, ctx, &mut result);
+
     // Trust class validation
-    if let Some(tc_val) = &header.trust_class {
-        if let Some(tc) = get_yaml_string(&Some(tc_val.clone())) {
-            if !VALID_TRUST_CLASSES.contains(&tc) {
-                result.errors.push(format!("Invalid trust_class: {}", tc));
-            }
-        }
+    if let Some(tc) = get_yaml_string(&header.trust_class)
+        && !VALID_TRUST_CLASSES.contains(&tc)
+    {
+        result.errors.push(format!("Invalid trust_class: {}", tc));
     }
 
     // Classification validation
@@ -363,12 +578,13 @@ fn validate_header(_path: &Path, content: &str) -> ValidationResult {
     }
 
     // Timestamp validation
-    if header.created.is_none() {
+    if is_yaml_null(&header.created) {
         result.errors.push("Missing created".to_string());
     }
-    if header.last_updated.is_none() {
+    if is_yaml_null(&header.last_updated) {
         result.errors.push("Missing last_updated".to_string());
     }
+    verify_timestamps(&header, &mut result);
 
     result
 }
@@ -378,10 +594,15 @@ fn validate_header(_path: &Path, content: &str) -> ValidationResult {
 pub fn run(repo_root_arg: Option<String>, file_path: Option<String>) -> Result<(), HeaderError> {
     let repo_root = match repo_root_arg {
         Some(path) => PathBuf::from(path),
-        None => find_repo_root()?,
+        None => find_repo_root()?.ok_or(HeaderError::NoSpecsDir)?,
     };
 
     let specs_dir = repo_root.join("specs");
+    let ctx = ValidationContext {
+        repo_root: fs::canonicalize(&repo_root)?,
+        keyring: keyring::load_keyring(&repo_root, None).ok(),
+        registry: unit::load_registry(&repo_root).ok(),
+    };
 
     println!("=== XONAIX LIBRARY HEADER VALIDATION ===");
     println!("Repository: {}", repo_root.display());
@@ -403,7 +624,7 @@ pub fn run(repo_root_arg: Option<String>, file_path: Option<String>) -> Result<(
         }
 
         let content = fs::read_to_string(&path)?;
-        let result = validate_header(&path, &content);
+        let result = validate_header(&path, &content, &ctx);
 
         files_checked = 1;
         total_errors = result.errors.len() as u32;
@@ -442,7 +663,7 @@ pub fn run(repo_root_arg: Option<String>, file_path: Option<String>) -> Result<(
                 Err(_) => continue,
             };
 
-            let result = validate_header(path, &content);
+            let result = validate_header(path, &content, &ctx);
             files_checked += 1;
 
             if !result.errors.is_empty() || !result.warnings.is_empty() {
@@ -476,3 +697,21 @@ pub fn run(repo_root_arg: Option<String>, file_path: Option<String>) -> Result<(
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn utc_timestamps_require_uppercase_z() {
+        assert!(parse_utc_timestamp("2025-01-02T03:04:05Z").is_ok());
+        assert!(parse_utc_timestamp("2025-01-02T03:04:05z").is_err());
+    }
+
+    #[test]
+    fn utc_timestamps_reject_offsets_and_garbage() {
+        assert!(parse_utc_timestamp("2025-01-02T03:04:05+00:00").is_err());
+        assert!(parse_utc_timestamp("2025-01-02T03:04:05+02:00").is_err());
+        assert!(parse_utc_timestamp("2025-01-02 03:04").is_err());
+    }
+}
@@ -0,0 +1,500 @@
+//! Signer keyring module.
+//!
+//! Loads the trusted Ed25519 public keys used to verify document signatures
+//! and decides which authority tiers and trust classes each signer may seal.
+//!
+//! Each signer may hold several keys over time. A key is only trusted for
+//! signatures whose `signed_at` falls inside its validity window. A revoked
+//! key is rejected for signatures at or after `revoked_at`; earlier signatures
+//! still verify and are reported so they can be redone.
+
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Repository-relative location of the signer keyring.
+pub(crate) const KEYRING_PATH: &str = "specs/_governance/SIGNER_KEYRING.json";
+
+/// Keyring format version understood by this tool.
+pub(crate) const KEYRING_VERSION: &str = "2.1.0";
+
+/// Authority tiers a signer may be authorized for.
+const VALID_AUTHORITY_TIERS: &[&str] = &["T0", "T1", "T2", "T3"];
+
+/// Trust classes a signer may be authorized for.
+const VALID_TRUST_CLASSES: &[&str] = &["L0", "L1", "L2", "L3", "L4"];
+
+/// Errors while loading or querying the keyring.
+#[derive(Debug, Error)]
+pub enum KeyringError {
+    #[error("Cannot read keyring {0}: {1}")]
+    Io(PathBuf, std::io::Error),
+
+    #[error("Keyring parse error in {0}: {1}")]
+    Json(PathBuf, serde_json::Error),
+
+    #[error("Signer not in keyring: {0}")]
+    UnknownSigner(String),
+
+    #[error("Unsupported keyring_version {0} (expected {1})")]
+    UnsupportedVersion(String, &'static str),
+
+    #[error("Signer {0} is not authorized to seal {1}")]
+    Unauthorized(String, String),
+
+    #[error("Signer {0} is not authorized to approve {1}")]
+    UnauthorizedApproval(String, String),
+
+    #[error("Signer {0} has no key valid at {1}")]
+    NoValidKey(String, String),
+
+    #[error("Signing key {1} is not listed for signer {0}")]
+    KeyNotListed(String, String),
+
+    #[error("Signing key {0} was revoked at {1}")]
+    KeyRevoked(String, String),
+
+    #[error("Signature by {0} does not verify against any key valid at {1}")]
+    BadSignature(String, String),
+
+    #[error("Signing key {0} was revoked at {1}, before the signature at {2}")]
+    SignedAfterRevocation(String, String, String),
+}
+
+/// SIGNER_KEYRING.json structure.
+#[derive(Debug, Deserialize)]
+pub(crate) struct Keyring {
+    pub keyring_version: String,
+    pub signers: BTreeMap<String, SignerEntry>,
+    #[serde(default)]
+    pub revocations: Vec<Revocation>,
+}
+
+/// A trusted signer identity.
+#[derive(Debug, Deserialize)]
+pub(crate) struct SignerEntry {
+    /// Authority tiers (T0-T3) this signer may seal.
+    #[serde(default)]
+    pub authority_tiers: Vec<String>,
+    /// Trust classes (L0-L4) this signer may seal.
+    #[serde(default)]
+    pub trust_classes: Vec<String>,
+    /// Whether this signer may seal Library releases (BOMs).
+    #[serde(default)]
+    pub release_signer: bool,
+    /// Party this signer approves documents for.
+    #[serde(default)]
+    pub party: Option<String>,
+    /// Roles this signer may approve documents as.
+    #[serde(default)]
+    pub approval_roles: Vec<String>,
+    /// Keys held by this signer, current and retired.
+    pub keys: Vec<SignerKey>,
+}
+
+/// One Ed25519 key with its validity window.
+#[derive(Debug, Deserialize)]
+pub(crate) struct SignerKey {
+    /// Keyring-unique key identifier.
+    pub key_id: String,
+    /// Hex-encoded Ed25519 public key (32 bytes).
+    pub public_key: String,
+    pub valid_from: DateTime<Utc>,
+    #[serde(default)]
+    pub valid_until: Option<DateTime<Utc>>,
+}
+
+/// Revocation of a key from a point in time onward.
+#[derive(Debug, Deserialize)]
+pub(crate) struct Revocation {
+    pub key_id: String,
+    pub revoked_at: DateTime<Utc>,
+    pub reason: String,
+}
+
+/// The key that produced a verified signature.
+#[derive(Debug)]
+pub(crate) struct KeyMatch {
+    pub key_id: String,
+    /// Set when the key was revoked after the anchored signature was made.
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl SignerKey {
+    fn is_valid_at(&self, at: DateTime<Utc>) -> bool {
+        self.valid_from <= at && self.valid_until.is_none_or(|until| at < until)
+    }
+}
+
+impl Keyring {
+    /// Revocation time of a key, if it has been revoked.
+    fn revoked_at(&self, key_id: &str) -> Option<DateTime<Utc>> {
+        self.revocations
+            .iter()
+            .filter(|r| r.key_id == key_id)
+            .map(|r| r.revoked_at)
+            .min()
+    }
+
+    /// Verify a signature made by `signer` at `signed_at`.
+    ///
+    /// Only keys whose validity window covers `signed_at` are tried. A key
+    /// revoked at or before `signed_at` is rejected. A key revoked later is
+    /// accepted and reported through [`KeyMatch::revoked_at`].
+    pub(crate) fn verify(
+        &self,
+        signer: &str,
+        signed_at: DateTime<Utc>,
+        message: &[u8],
+        signature: &Signature,
+    ) -> Result<KeyMatch, KeyringError> {
+        let entry = self
+            .signers
+            .get(signer)
+            .ok_or_else(|| KeyringError::UnknownSigner(signer.to_string()))?;
+
+        let at = signed_at.to_rfc3339();
+        let candidates: Vec<&SignerKey> = entry.keys.iter().filter(|k| k.is_valid_at(signed_at)).collect();
+        if candidates.is_empty() {
+            return Err(KeyringError::NoValidKey(signer.to_string(), at));
+        }
+
+        let key = candidates
+            .into_iter()
+            .find(|k| {
+                parse_verifying_key(&k.public_key)
+                    .map(|vk| vk.verify(message, signature).is_ok())
+                    .unwrap_or(false)
+            })
+            .ok_or_else(|| KeyringError::BadSignature(signer.to_string(), at.clone()))?;
+
+        let revoked_at = self.revoked_at(&key.key_id);
+        if let Some(revoked) = revoked_at
+            && revoked <= signed_at
+        {
+            return Err(KeyringError::SignedAfterRevocation(key.key_id.clone(), revoked.to_rfc3339(), at));
+        }
+
+        Ok(KeyMatch {
+            key_id: key.key_id.clone(),
+            revoked_at,
+        })
+    }
+
+    /// Find the key of `signer` matching `public_key` that may sign at `at`.
+    ///
+    /// Used before signing, so nothing is written with a key the keyring
+    /// would reject on verification.
+    pub(crate) fn signing_key(
+        &self,
+        signer: &str,
+        public_key: &VerifyingKey,
+        at: DateTime<Utc>,
+    ) -> Result<&SignerKey, KeyringError> {
+        let entry = self
+            .signers
+            .get(signer)
+            .ok_or_else(|| KeyringError::UnknownSigner(signer.to_string()))?;
+
+        let key = entry
+            .keys
+            .iter()
+            .find(|k| parse_verifying_key(&k.public_key).is_ok_and(|vk| vk == *public_key))
+            .ok_or_else(|| KeyringError::KeyNotListed(signer.to_string(), hex::encode(public_key.to_bytes())))?;
+
+        if !key.is_valid_at(at) {
+            return Err(KeyringError::NoValidKey(signer.to_string(), at.to_rfc3339()));
+        }
+        if let Some(revoked) = self.revoked_at(&key.key_id)
+            && revoked <= at
+        {
+            return Err(KeyringError::KeyRevoked(key.key_id.clone(), revoked.to_rfc3339()));
+        }
+
+        Ok(key)
+    }
+
+    /// Check that a signer may seal a document with the given tier and trust class.
+    ///
+    /// Compound trust classes such as `L1/L2` require every component.
+    /// Documents without a trust class only require tier authorization.
+    pub(crate) fn authorize(
+        &self,
+        signer: &str,
+        authority_tier: Option<&str>,
+        trust_class: Option<&str>,
+    ) -> Result<(), KeyringError> {
+        let entry = self
+            .signers
+            .get(signer)
+            .ok_or_else(|| KeyringError::UnknownSigner(signer.to_string()))?;
+
+        let tier = authority_tier.unwrap_or("no authority_tier");
+        if !entry.authority_tiers.iter().any(|t| t == tier) {
+            return Err(KeyringError::Unauthorized(signer.to_string(), format!("authority_tier {}", tier)));
+        }
+
+        if let Some(tc) = trust_class {
+            for class in tc.split('/') {
+                if !entry.trust_classes.iter().any(|c| c == class) {
+                    return Err(KeyringError::Unauthorized(signer.to_string(), format!("trust_class {}", tc)));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check that a signer may seal a Library release BOM.
+    pub(crate) fn authorize_release(&self, signer: &str) -> Result<(), KeyringError> {
+        let entry = self
+            .signers
+            .get(signer)
+            .ok_or_else(|| KeyringError::UnknownSigner(signer.to_string()))?;
+
+        if entry.release_signer {
+            Ok(())
+        } else {
+            Err(KeyringError::Unauthorized(signer.to_string(), "Library releases".to_string()))
+        }
+    }
+
+    /// Check that a signer may approve documents for `party` as `role`.
+    pub(crate) fn authorize_approval(&self, signer: &str, party: &str, role: &str) -> Result<(), KeyringError> {
+        let entry = self
+            .signers
+            .get(signer)
+            .ok_or_else(|| KeyringError::UnknownSigner(signer.to_string()))?;
+
+        if entry.party.as_deref() != Some(party) {
+            return Err(KeyringError::UnauthorizedApproval(signer.to_string(), format!("for party {}", party)));
+        }
+        if !entry.approval_roles.iter().any(|r| r == role) {
+            return Err(KeyringError::UnauthorizedApproval(signer.to_string(), format!("as role {}", role)));
+        }
+
+        Ok(())
+    }
+
+    /// Check every signer entry for malformed keys, unknown tiers or classes,
+    /// inverted validity windows and dangling or unexplained revocations.
+    pub(crate) fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        let mut key_ids: HashSet<&str> = HashSet::new();
+
+        for (signer, entry) in &self.signers {
+            if entry.keys.is_empty() {
+                problems.push(format!("{}: no keys", signer));
+            }
+            for key in &entry.keys {
+                if !key_ids.insert(key.key_id.as_str()) {
+                    problems.push(format!("{}: duplicate key_id: {}", signer, key.key_id));
+                }
+                if let Err(reason) = parse_verifying_key(&key.public_key) {
+                    problems.push(format!("{}: key {}: invalid public_key: {}", signer, key.key_id, reason));
+                }
+                if let Some(until) = key.valid_until
+                    && until <= key.valid_from
+                {
+                    problems.push(format!("{}: key {}: valid_until is not after valid_from", signer, key.key_id));
+                }
+            }
+            for tier in &entry.authority_tiers {
+                if !VALID_AUTHORITY_TIERS.contains(&tier.as_str()) {
+                    problems.push(format!("{}: invalid authority tier: {}", signer, tier));
+                }
+            }
+            for class in &entry.trust_classes {
+                if !VALID_TRUST_CLASSES.contains(&class.as_str()) {
+                    problems.push(format!("{}: invalid trust class: {}", signer, class));
+                }
+            }
+        }
+
+        for revocation in &self.revocations {
+            if !key_ids.contains(revocation.key_id.as_str()) {
+                problems.push(format!("revocation references unknown key_id: {}", revocation.key_id));
+            }
+            if revocation.reason.trim().is_empty() {
+                problems.push(format!("revocation of {} has no reason", revocation.key_id));
+            }
+        }
+
+        problems
+    }
+}
+
+/// Parse a hex-encoded Ed25519 public key.
+pub(crate) fn parse_verifying_key(hex_key: &str) -> Result<VerifyingKey, String> {
+    let bytes = hex::decode(hex_key.trim()).map_err(|e| e.to_string())?;
+    let bytes: [u8; 32] = bytes
+        .try_into()
+        .map_err(|b: Vec<u8>| format!("expected 32 bytes, got {}", b.len()))?;
+    VerifyingKey::from_bytes(&bytes).map_err(|e| e.to_string())
+}
+
+/// Load a keyring from an explicit path, or from the repository default.
+pub(crate) fn load_keyring(repo_root: &Path, path: Option<&Path>) -> Result<Keyring, KeyringError> {
+    let keyring_path = match path {
+        Some(p) => p.to_path_buf(),
+        None => repo_root.join(KEYRING_PATH),
+    };
+
+    let content = fs::read_to_string(&keyring_path).map_err(|e| KeyringError::Io(keyring_path.clone(), e))?;
+    let keyring: Keyring = serde_json::from_str(&content).map_err(|e| KeyringError::Json(keyring_path, e))?;
+
+    if keyring.keyring_version != KEYRING_VERSION {
+        return Err(KeyringError::UnsupportedVersion(keyring.keyring_version, KEYRING_VERSION));
+    }
+
+    Ok(keyring)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    pub(crate) fn ts(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value).map(|t| t.with_timezone(&Utc)).unwrap_or_default()
+    }
+
+    pub(crate) fn test_key() -> SigningKey {
+        SigningKey::from_bytes(&[7u8; 32])
+    }
+
+    /// Key of `bob`, who approves as `customer` for party `Bank X`.
+    pub(crate) fn bob_key() -> SigningKey {
+        SigningKey::from_bytes(&[8u8; 32])
+    }
+
+    /// A keyring with signer `alice` holding `test_key()` from 2025, optionally
+    /// revoked, and approver `bob`.
+    pub(crate) fn test_keyring(revoked_at: Option<&str>) -> Keyring {
+        let mut signers = BTreeMap::new();
+        let _ = signers.insert(
+            "bob".to_string(),
+            SignerEntry {
+                authority_tiers: Vec::new(),
+                trust_classes: Vec::new(),
+                release_signer: false,
+                party: Some("Bank X".to_string()),
+                approval_roles: vec!["customer".to_string()],
+                keys: vec![SignerKey {
+                    key_id: "bob-2025".to_string(),
+                    public_key: hex::encode(bob_key().verifying_key().to_bytes()),
+                    valid_from: ts("2025-01-01T00:00:00Z"),
+                    valid_until: None,
+                }],
+            },
+        );
+        let _ = signers.insert(
+            "alice".to_string(),
+            SignerEntry {
+                authority_tiers: vec!["T1".to_string()],
+                trust_classes: vec!["L1".to_string(), "L2".to_string()],
+                release_signer: true,
+                party: Some("Xonaix".to_string()),
+                approval_roles: vec!["author".to_string()],
+                keys: vec![SignerKey {
+                    key_id: "alice-2025".to_string(),
+                    public_key: hex::encode(test_key().verifying_key().to_bytes()),
+                    valid_from: ts("2025-01-01T00:00:00Z"),
+                    valid_until: None,
+                }],
+            },
+        );
+        Keyring {
+            keyring_version: KEYRING_VERSION.to_string(),
+            signers,
+            revocations: revoked_at
+                .map(|at| Revocation {
+                    key_id: "alice-2025".to_string(),
+                    revoked_at: ts(at),
+                    reason: "compromised".to_string(),
+                })
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn verifies_signature_inside_validity_window() {
+        let sig = test_key().sign(b"message");
+        let key = test_keyring(None).verify("alice", ts("2025-06-01T00:00:00Z"), b"message", &sig);
+        assert!(key.is_ok_and(|k| k.key_id == "alice-2025" && k.revoked_at.is_none()));
+    }
+
+    #[test]
+    fn rejects_signature_before_validity_window() {
+        let sig = test_key().sign(b"message");
+        let result = test_keyring(None).verify("alice", ts("2024-06-01T00:00:00Z"), b"message", &sig);
+        assert!(matches!(result, Err(KeyringError::NoValidKey(..))));
+    }
+
+    #[test]
+    fn rejects_wrong_message() {
+        let sig = test_key().sign(b"message");
+        let result = test_keyring(None).verify("alice", ts("2025-06-01T00:00:00Z"), b"other", &sig);
+        assert!(matches!(result, Err(KeyringError::BadSignature(..))));
+    }
+
+    #[test]
+    fn rejects_signature_after_revocation() {
+        let sig = test_key().sign(b"message");
+        let keyring = test_keyring(Some("2025-03-01T00:00:00Z"));
+        let result = keyring.verify("alice", ts("2025-06-01T00:00:00Z"), b"message", &sig);
+        assert!(matches!(result, Err(KeyringError::SignedAfterRevocation(..))));
+    }
+
+    /// Signatures made before the revocation still verify and report it.
+    #[test]
+    fn reports_key_revoked_after_signing() {
+        let sig = test_key().sign(b"message");
+        let keyring = test_keyring(Some("2025-03-01T00:00:00Z"));
+        let key = keyring.verify("alice", ts("2025-02-01T00:00:00Z"), b"message", &sig);
+        assert!(key.is_ok_and(|k| k.revoked_at == Some(ts("2025-03-01T00:00:00Z"))));
+    }
+
+    #[test]
+    fn signing_key_must_be_listed_valid_and_unrevoked() {
+        let vk = test_key().verifying_key();
+        let other = SigningKey::from_bytes(&[8u8; 32]).verifying_key();
+        let now = ts("2025-06-01T00:00:00Z");
+
+        assert!(test_keyring(None).signing_key("alice", &vk, now).is_ok_and(|k| k.key_id == "alice-2025"));
+        assert!(matches!(test_keyring(None).signing_key("carol", &vk, now), Err(KeyringError::UnknownSigner(_))));
+        assert!(matches!(test_keyring(None).signing_key("alice", &other, now), Err(KeyringError::KeyNotListed(..))));
+        assert!(matches!(
+            test_keyring(None).signing_key("alice", &vk, ts("2024-06-01T00:00:00Z")),
+            Err(KeyringError::NoValidKey(..))
+        ));
+        assert!(matches!(
+            test_keyring(Some("2025-03-01T00:00:00Z")).signing_key("alice", &vk, now),
+            Err(KeyringError::KeyRevoked(..))
+        ));
+    }
+
+    #[test]
+    fn authorize_requires_every_trust_class_component() {
+        let keyring = test_keyring(None);
+        assert!(keyring.authorize("alice", Some("T1"), Some("L1/L2")).is_ok());
+        assert!(keyring.authorize("alice", Some("T1"), Some("L2/L3")).is_err());
+        assert!(keyring.authorize("alice", Some("T0"), None).is_err());
+        assert!(keyring.authorize("bob", Some("T1"), None).is_err());
+    }
+
+    #[test]
+    fn validate_requires_a_revocation_reason() {
+        let mut keyring = test_keyring(Some("2025-03-01T00:00:00Z"));
+        assert!(keyring.validate().is_empty());
+        for revocation in &mut keyring.revocations {
+            revocation.reason = " ".to_string();
+        }
+        assert_eq!(keyring.validate(), vec!["revocation of alice-2025 has no reason".to_string()]);
+    }
+}
@@ -3,23 +3,49 @@
 //! Single Rust binary for all governance and manifest tooling in xonaix-library.
 //!
 //! Commands:
-//! - generate-manifest: Generate SHA-256 manifest for governance or units
+//! - generate-manifest: Generate SHA3-512 or SHA-256 manifests for governance or units
+//! - manifest: Verify an existing manifest against the files on disk
 //! - enforce: Run all no-debt enforcement checks
 //! - unit-validate: Validate UNIT.json files against registry
 //! - graph-verify: Verify dependency graph integrity (DAG, no cycles)
 //! - doctor: Verify environment and library requirements
 //! - header-validate: Validate document headers against v2.1 schema
 //! - governance-report: Generate governance metrics and reports
+//! - seal: Compute SHA3-512 content hashes for approved documents
+//! - sign: Sign document content hashes with Ed25519
+//! - verify-signature: Verify document signatures against the signer keyring
+//! - bom: Generate, stage, seal and verify the Library BOM
+//! - merkle: Prove and verify file inclusion against manifest and BOM Merkle roots
+//! - anchor: Anchor sealed documents in the ZeroPoint ledger and verify anchors
+//! - engagement: Validate the engagement registry and compute engagement readiness
+//! - gate: Report whether units are sealed, as JSON, for code generators
+//! - conformance: Verify constitutional conformance against vendored Constitution and Zero Point
+//! - header: Normalize document frontmatter and migrate it between schema versions
 
 use clap::{Parser, Subcommand};
 use std::process::ExitCode;
 
+mod anchor;
+mod approval;
+mod bom;
+mod canonical;
+mod common;
+mod conformance;
 mod doctor;
+mod engagement;
 mod enforce;
+mod frontmatter;
+mod gate;
 mod manifest;
+mod merkle;
+mod migrate;
 mod header;
+mod keyring;
 mod report;
+mod seal;
+mod sign;
 mod unit;
+mod version;
 
 #[derive(Parser)]
 #[command(name = "xonaix-library-tools")]
@@ -32,16 +58,20 @@ struct Cli {
 
 #[derive(Subcommand)]
 enum Commands {
-    /// Generate SHA-256 manifest for governance or units
+    /// Generate SHA3-512 or SHA-256 manifest for governance or units
     GenerateManifest {
         /// Generate governance manifest
         #[arg(long)]
         governance: bool,
 
         /// Generate manifest for a specific unit (by unit_id)
-        #[arg(long)]
+        #[arg(long, conflicts_with = "all_units")]
         unit: Option<String>,
 
+        /// Generate manifests for every unit in the registry
+        #[arg(long)]
+        all_units: bool,
+
         /// Output file path (default: auto-generated)
         #[arg(long)]
         output: Option<String>,
@@ -53,6 +83,20 @@ enum Commands {
         /// Check mode: regenerate and compare to existing, fail if different
         #[arg(long)]
         check: bool,
+
+        /// Pin generated_at to an RFC 3339 timestamp (overrides SOURCE_DATE_EPOCH)
+        #[arg(long)]
+        timestamp: Option<String>,
+
+        /// File hash algorithm: sha256, sha3-512 (default: the existing manifest's, else sha3-512)
+        #[arg(long)]
+        hash_alg: Option<String>,
+    },
+
+    /// Manifest operations
+    Manifest {
+        #[command(subcommand)]
+        action: ManifestAction,
     },
 
     /// Run all no-debt enforcement checks
@@ -108,7 +152,7 @@ enum Commands {
         #[arg(long)]
         repo_root: Option<String>,
 
-        /// Output format: json, json-pretty, table, summary
+        /// Output format: json (canonical, RFC 8785), json-pretty, table, summary
         #[arg(long, default_value = "table")]
         format: String,
 
@@ -116,6 +160,383 @@ enum Commands {
         #[arg(long)]
         output: Option<String>,
     },
+
+    /// Compute and record document content hashes
+    Seal {
+        #[command(subcommand)]
+        action: SealAction,
+    },
+
+    /// Sign approved documents with an Ed25519 key and mark them sealed
+    Sign {
+        /// Repository root path (default: auto-detect)
+        #[arg(long)]
+        repo_root: Option<String>,
+
+        /// Specific file path to sign (default: all approved, hashed markdown files)
+        #[arg(long)]
+        file: Option<String>,
+
+        /// Path to the signing key file (hex-encoded 32-byte Ed25519 seed)
+        #[arg(long)]
+        key: String,
+
+        /// Signer identity recorded in integrity.signed_by
+        #[arg(long)]
+        signer: String,
+
+        /// Keyring file path (default: specs/_governance/SIGNER_KEYRING.json)
+        #[arg(long)]
+        keyring: Option<String>,
+    },
+
+    /// Verify document signatures against the trusted signer keyring
+    VerifySignature {
+        /// Repository root path (default: auto-detect)
+        #[arg(long)]
+        repo_root: Option<String>,
+
+        /// Specific file path to verify (default: all markdown files)
+        #[arg(long)]
+        file: Option<String>,
+
+        /// Keyring file path (default: specs/_governance/SIGNER_KEYRING.json)
+        #[arg(long)]
+        keyring: Option<String>,
+    },
+
+    /// Library BOM (Bill of Materials) operations
+    Bom {
+        #[command(subcommand)]
+        action: BomAction,
+    },
+
+    /// Merkle inclusion proofs
+    Merkle {
+        #[command(subcommand)]
+        action: MerkleAction,
+    },
+
+    /// ZeroPoint anchoring of sealed documents
+    Anchor {
+        #[command(subcommand)]
+        action: AnchorAction,
+    },
+
+    /// Customer engagement registry operations
+    Engagement {
+        #[command(subcommand)]
+        action: EngagementAction,
+    },
+
+    /// Constitutional conformance verification
+    Conformance {
+        #[command(subcommand)]
+        action: ConformanceAction,
+    },
+
+    /// Document header maintenance
+    Header {
+        #[command(subcommand)]
+        action: HeaderAction,
+    },
+
+    /// Check that units are sealed before code generation (JSON output, audited)
+    Gate {
+        /// Unit IDs the generator depends on
+        #[arg(required = true)]
+        unit_ids: Vec<String>,
+
+        /// Pin the check to a sealed Library BOM version (LIB-X.Y.Z)
+        #[arg(long)]
+        bom: Option<String>,
+
+        /// Repository root path (default: auto-detect)
+        #[arg(long)]
+        repo_root: Option<String>,
+
+        /// Path to the signer keyring (default: specs/_governance/SIGNER_KEYRING.json)
+        #[arg(long)]
+        keyring: Option<String>,
+
+        /// Identity of the requesting pipeline, recorded in the audit log
+        #[arg(long)]
+        requested_by: Option<String>,
+
+        /// Append the answer to this audit log, outside the specs tree (default: no audit record)
+        #[arg(long)]
+        audit_log: Option<String>,
+
+        /// Output file path (default: stdout)
+        #[arg(long)]
+        output: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum HeaderAction {
+    /// Rewrite headers into canonical v2.1 field order and section comments
+    Normalize {
+        /// Repository root path (default: auto-detect)
+        #[arg(long)]
+        repo_root: Option<String>,
+
+        /// Specific file to normalize (default: all markdown files)
+        #[arg(long)]
+        file: Option<String>,
+
+        /// Check mode: report files that are not normalized, write nothing
+        #[arg(long)]
+        check: bool,
+    },
+
+    /// Migrate headers to a newer schema version (contract §14, §15)
+    Migrate {
+        /// Target schema version
+        #[arg(long, default_value = "2.1")]
+        to: String,
+
+        /// Repository root path (default: auto-detect)
+        #[arg(long)]
+        repo_root: Option<String>,
+
+        /// Specific file to migrate (default: all markdown files)
+        #[arg(long)]
+        file: Option<String>,
+
+        /// Print a unified diff per file instead of writing
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConformanceAction {
+    /// Check constitutional_conformance hashes, last_verified and deviations
+    Verify {
+        /// Repository root path (default: auto-detect)
+        #[arg(long)]
+        repo_root: Option<String>,
+
+        /// Specific file to verify (default: all markdown files)
+        #[arg(long)]
+        file: Option<String>,
+
+        /// Days after which last_verified is reported as stale
+        #[arg(long, default_value_t = conformance::DEFAULT_MAX_AGE_DAYS)]
+        max_age_days: i64,
+
+        /// Fail documents that do not declare conformance
+        #[arg(long)]
+        strict: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum EngagementAction {
+    /// Validate the engagement registry against UNIT_REGISTRY.json
+    Validate {
+        /// Repository root path (default: auto-detect)
+        #[arg(long)]
+        repo_root: Option<String>,
+
+        /// Engagement registry path (default: specs/_governance/ENGAGEMENT_REGISTRY.json)
+        #[arg(long)]
+        registry: Option<String>,
+    },
+
+    /// Compute engagement readiness from the headers of the required specs
+    Readiness {
+        /// Repository root path (default: auto-detect)
+        #[arg(long)]
+        repo_root: Option<String>,
+
+        /// Engagement registry path (default: specs/_governance/ENGAGEMENT_REGISTRY.json)
+        #[arg(long)]
+        registry: Option<String>,
+
+        /// Only this engagement (default: all engagements)
+        #[arg(long)]
+        engagement: Option<String>,
+
+        /// Write the computed readiness back into the registry
+        #[arg(long)]
+        write: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum AnchorAction {
+    /// Anchor sealed, unanchored documents in a new ledger block
+    Submit {
+        /// Repository root path (default: auto-detect)
+        #[arg(long)]
+        repo_root: Option<String>,
+
+        /// Anchor only this file (default: all sealed documents)
+        #[arg(long)]
+        file: Option<String>,
+
+        /// Ledger to anchor in (default: xzp-local)
+        #[arg(long)]
+        ledger_id: Option<String>,
+
+        /// Ledger file path (default: .xonaix/ledger/<ledger_id>.jsonl)
+        #[arg(long)]
+        ledger: Option<String>,
+    },
+
+    /// Verify document anchors and the ledger chain offline
+    Verify {
+        /// Repository root path (default: auto-detect)
+        #[arg(long)]
+        repo_root: Option<String>,
+
+        /// Verify only this file (default: all anchored documents)
+        #[arg(long)]
+        file: Option<String>,
+
+        /// Ledger file path (default: .xonaix/ledger/<ledger_id>.jsonl)
+        #[arg(long)]
+        ledger: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum MerkleAction {
+    /// Emit an inclusion proof for a file in its unit manifest (and BOM)
+    Prove {
+        /// File to prove
+        file: String,
+
+        /// Extend the proof to the BOM root of this library version (LIB-X.Y.Z)
+        #[arg(long)]
+        version: Option<String>,
+
+        /// Repository root path (default: auto-detect)
+        #[arg(long)]
+        repo_root: Option<String>,
+
+        /// Output file path (default: stdout)
+        #[arg(long)]
+        output: Option<String>,
+    },
+
+    /// Check an inclusion proof against a trusted Merkle root
+    Verify {
+        /// Proof file produced by `merkle prove`
+        proof: String,
+
+        /// Trusted Merkle root (hex), e.g. the merkle_root of a sealed BOM
+        #[arg(long)]
+        root: String,
+
+        /// Also check this file's content against the proof's file_hash
+        #[arg(long)]
+        file: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ManifestAction {
+    /// Rehash every file listed in an existing manifest and report discrepancies
+    Verify {
+        /// Manifest file to verify
+        file: String,
+
+        /// Repository root path (default: auto-detect)
+        #[arg(long)]
+        repo_root: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum BomAction {
+    /// Generate the initial Library BOM from all registered units
+    Generate {
+        /// Library version (LIB-X.Y.Z)
+        #[arg(long)]
+        version: String,
+
+        /// Repository root path (default: auto-detect)
+        #[arg(long)]
+        repo_root: Option<String>,
+
+        /// Output file path (default: specs/_governance/manifests/BOM_{version}.json)
+        #[arg(long)]
+        output: Option<String>,
+    },
+
+    /// Stage the next BOM: seal new and version-changed units, reference the rest
+    Stage {
+        /// Library version (LIB-X.Y.Z)
+        #[arg(long)]
+        version: String,
+
+        /// Previous sealed release to diff against (default: latest sealed)
+        #[arg(long)]
+        previous: Option<String>,
+
+        /// Repository root path (default: auto-detect)
+        #[arg(long)]
+        repo_root: Option<String>,
+
+        /// Output file path (default: specs/_governance/manifests/BOM_{version}.json)
+        #[arg(long)]
+        output: Option<String>,
+    },
+
+    /// Seal a generated BOM with a detached Ed25519 signature
+    Seal {
+        /// Library version (LIB-X.Y.Z)
+        #[arg(long)]
+        version: String,
+
+        /// Repository root path (default: auto-detect)
+        #[arg(long)]
+        repo_root: Option<String>,
+
+        /// Path to the signing key file (hex-encoded 32-byte Ed25519 seed)
+        #[arg(long)]
+        key: String,
+
+        /// Signer identity recorded in the seal
+        #[arg(long)]
+        signer: String,
+
+        /// Keyring file path (default: specs/_governance/SIGNER_KEYRING.json)
+        #[arg(long)]
+        keyring: Option<String>,
+    },
+
+    /// Verify a sealed release against the checked-out tree (offline)
+    Verify {
+        /// Library version (LIB-X.Y.Z)
+        #[arg(long)]
+        version: String,
+
+        /// Repository root path (default: auto-detect)
+        #[arg(long)]
+        repo_root: Option<String>,
+
+        /// Keyring file path (default: specs/_governance/SIGNER_KEYRING.json)
+        #[arg(long)]
+        keyring: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum SealAction {
+    /// Compute SHA3-512 content hashes for approved documents
+    Hash {
+        /// Repository root path (default: auto-detect)
+        #[arg(long)]
+        repo_root: Option<String>,
+
+        /// Specific file path to hash (default: all approved markdown files)
+        #[arg(long)]
+        file: Option<String>,
+    },
 }
 
 fn main() -> ExitCode {
@@ -125,10 +546,13 @@ fn main() -> ExitCode {
         Commands::GenerateManifest {
             governance,
             unit,
+            all_units,
             output,
             repo_root,
             check,
-        } => match manifest::run(governance, unit, output, repo_root, check) {
+            timestamp,
+            hash_alg,
+        } => match manifest::run(governance, unit, all_units, output, repo_root, check, timestamp, hash_alg) {
             Ok(()) => true,
             Err(e) => {
                 eprintln!("ERROR: {e}");
@@ -136,6 +560,15 @@ fn main() -> ExitCode {
             }
         },
 
+        Commands::Manifest { action } => match action {
+            ManifestAction::Verify { file, repo_root } => match manifest::verify(file, repo_root) {
+                Ok(()) => true,
+                Err(e) => {
+                    eprintln!("ERROR: {e}");
+                    false
+                }
+            },
+        },
         Commands::Enforce { repo_root, current_only } => match enforce::run(repo_root, current_only) {
             Ok(()) => true,
             Err(e) => {
@@ -192,6 +625,178 @@ fn main() -> ExitCode {
                 }
             }
         }
+
+        Commands::Seal { action } => match action {
+            SealAction::Hash { repo_root, file } => match seal::hash(repo_root, file) {
+                Ok(()) => true,
+                Err(e) => {
+                    eprintln!("ERROR: {e}");
+                    false
+                }
+            },
+        },
+
+        Commands::Sign {
+            repo_root,
+            file,
+            key,
+            signer,
+            keyring,
+        } => match sign::sign(repo_root, file, key, signer, keyring) {
+            Ok(()) => true,
+            Err(e) => {
+                eprintln!("ERROR: {e}");
+                false
+            }
+        },
+
+        Commands::VerifySignature { repo_root, file, keyring } => match sign::verify(repo_root, file, keyring) {
+            Ok(()) => true,
+            Err(e) => {
+                eprintln!("ERROR: {e}");
+                false
+            }
+        },
+
+        Commands::Bom { action } => match action {
+            BomAction::Generate { version, repo_root, output } => match bom::generate(repo_root, version, output) {
+                Ok(()) => true,
+                Err(e) => {
+                    eprintln!("ERROR: {e}");
+                    false
+                }
+            },
+            BomAction::Stage { version, previous, repo_root, output } => {
+                match bom::stage(repo_root, version, previous, output) {
+                    Ok(()) => true,
+                    Err(e) => {
+                        eprintln!("ERROR: {e}");
+                        false
+                    }
+                }
+            }
+            BomAction::Seal {
+                version,
+                repo_root,
+                key,
+                signer,
+                keyring,
+            } => match bom::seal(repo_root, version, key, signer, keyring) {
+                Ok(()) => true,
+                Err(e) => {
+                    eprintln!("ERROR: {e}");
+                    false
+                }
+            },
+            BomAction::Verify { version, repo_root, keyring } => match bom::verify(repo_root, version, keyring) {
+                Ok(()) => true,
+                Err(e) => {
+                    eprintln!("ERROR: {e}");
+                    false
+                }
+            },
+        },
+        Commands::Merkle { action } => match action {
+            MerkleAction::Prove { file, version, repo_root, output } => {
+                match merkle::prove_file(repo_root, file, version, output) {
+                    Ok(()) => true,
+                    Err(e) => {
+                        eprintln!("ERROR: {e}");
+                        false
+                    }
+                }
+            }
+            MerkleAction::Verify { proof, root, file } => match merkle::verify(proof, root, file) {
+                Ok(()) => true,
+                Err(e) => {
+                    eprintln!("ERROR: {e}");
+                    false
+                }
+            },
+        },
+
+        Commands::Anchor { action } => match action {
+            AnchorAction::Submit { repo_root, file, ledger_id, ledger } => {
+                match anchor::submit(repo_root, file, ledger_id, ledger) {
+                    Ok(()) => true,
+                    Err(e) => {
+                        eprintln!("ERROR: {e}");
+                        false
+                    }
+                }
+            }
+            AnchorAction::Verify { repo_root, file, ledger } => match anchor::verify(repo_root, file, ledger) {
+                Ok(()) => true,
+                Err(e) => {
+                    eprintln!("ERROR: {e}");
+                    false
+                }
+            },
+        },
+
+        Commands::Gate {
+            unit_ids,
+            bom,
+            repo_root,
+            keyring,
+            requested_by,
+            audit_log,
+            output,
+        } => match gate::run(repo_root, unit_ids, bom, keyring, requested_by, audit_log, output) {
+            Ok(()) => true,
+            Err(e) => {
+                eprintln!("ERROR: {e}");
+                false
+            }
+        },
+
+        Commands::Header { action } => match action {
+            HeaderAction::Normalize { repo_root, file, check } => match frontmatter::normalize(repo_root, file, check) {
+                Ok(()) => true,
+                Err(e) => {
+                    eprintln!("ERROR: {e}");
+                    false
+                }
+            },
+            HeaderAction::Migrate { to, repo_root, file, dry_run } => match migrate::migrate(repo_root, file, to, dry_run) {
+                Ok(()) => true,
+                Err(e) => {
+                    eprintln!("ERROR: {e}");
+                    false
+                }
+            },
+        },
+
+        Commands::Conformance { action } => match action {
+            ConformanceAction::Verify { repo_root, file, max_age_days, strict } => {
+                match conformance::verify(repo_root, file, max_age_days, strict) {
+                    Ok(()) => true,
+                    Err(e) => {
+                        eprintln!("ERROR: {e}");
+                        false
+                    }
+                }
+            }
+        },
+
+        Commands::Engagement { action } => match action {
+            EngagementAction::Validate { repo_root, registry } => match engagement::validate(repo_root, registry) {
+                Ok(()) => true,
+                Err(e) => {
+                    eprintln!("ERROR: {e}");
+                    false
+                }
+            },
+            EngagementAction::Readiness { repo_root, registry, engagement, write } => {
+                match engagement::readiness(repo_root, registry, engagement, write) {
+                    Ok(()) => true,
+                    Err(e) => {
+                        eprintln!("ERROR: {e}");
+                        false
+                    }
+                }
+            }
+        },
     };
 
     if success {
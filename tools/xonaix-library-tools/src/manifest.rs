@@ -1,15 +1,32 @@
 //! Manifest generation module.
 //!
-//! Generates deterministic SHA-256 manifests for governance and units.
+//! Generates deterministic manifests for governance and units, and verifies
+//! an existing manifest by rehashing the files it lists.
+//!
+//! Manifest v3.0.0 records the per-file hash algorithm in `hash_alg` and
+//! defaults to SHA3-512. Legacy v2.0.0 manifests (SHA-256, per-file `sha256`
+//! field) are still read, checked and refreshed in their own format.
+//!
+//! Manifests are written pretty-printed; their hash (as recorded in a BOM) is
+//! taken over the canonical JSON form, see [`crate::canonical`].
+//!
+//! `generated_at` can be pinned with `--timestamp` or `SOURCE_DATE_EPOCH` so
+//! identical trees produce byte-identical manifests.
 
 use chrono::{DateTime, Utc};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs::{self, File};
 use std::io::{BufReader, Read, Write};
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 use thiserror::Error;
 use walkdir::WalkDir;
-use sha2::{Sha256, Digest};
+use sha2::{Digest, Sha256};
+use sha3::Sha3_512;
+
+use crate::canonical;
+use crate::common;
+use crate::merkle::{self, InvalidHash};
 
 /// Errors that can occur during manifest generation.
 #[derive(Debug, Error)]
@@ -31,81 +48,341 @@ pub enum ManifestError {
 
     #[error("Manifest drift detected: {0}")]
     ManifestDrift(String),
+
+    #[error("Manifest generation failed: {0} of {1} manifests")]
+    GenerationFailed(usize, usize),
+
+    #[error("Unsupported hash algorithm: {0} (expected sha256 or sha3-512)")]
+    UnsupportedHashAlg(String),
+
+    #[error(transparent)]
+    InvalidHash(#[from] InvalidHash),
+
+    #[error("Invalid timestamp: {0}")]
+    InvalidTimestamp(String),
+
+    #[error("Manifest verification failed: {0} file(s) with discrepancies")]
+    VerificationFailed(usize),
+}
+
+/// Manifest format written for new manifests.
+const MANIFEST_VERSION: &str = "3.0.0";
+
+/// Legacy manifest format: SHA-256 only, no `hash_alg`, per-file `sha256`.
+const LEGACY_MANIFEST_VERSION: &str = "2.0.0";
+
+/// Hash algorithm for manifest file entries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HashAlg {
+    Sha256,
+    #[default]
+    Sha3_512,
+}
+
+impl HashAlg {
+    /// Name recorded in a manifest's `hash_alg` field.
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            HashAlg::Sha256 => "SHA-256",
+            HashAlg::Sha3_512 => "SHA3-512",
+        }
+    }
+
+    /// Suffix naming manifest files whose entries use this algorithm.
+    pub(crate) fn file_suffix(self) -> &'static str {
+        match self {
+            HashAlg::Sha256 => "sha256",
+            HashAlg::Sha3_512 => "sha3-512",
+        }
+    }
+
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "SHA-256" => Some(HashAlg::Sha256),
+            "SHA3-512" => Some(HashAlg::Sha3_512),
+            _ => None,
+        }
+    }
+}
+
+impl std::str::FromStr for HashAlg {
+    type Err = ManifestError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "sha256" => Ok(HashAlg::Sha256),
+            "sha3-512" => Ok(HashAlg::Sha3_512),
+            _ => Err(ManifestError::UnsupportedHashAlg(s.to_string())),
+        }
+    }
+}
+
+/// Manifest version and hash algorithm to generate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ManifestFormat {
+    version: &'static str,
+    pub hash_alg: HashAlg,
+}
+
+impl ManifestFormat {
+    /// Current (v3) format with the given hash algorithm.
+    pub(crate) fn new(hash_alg: HashAlg) -> Self {
+        ManifestFormat {
+            version: MANIFEST_VERSION,
+            hash_alg,
+        }
+    }
+
+    /// Format of an existing manifest.
+    pub(crate) fn of_manifest(manifest: &Manifest) -> Result<Self, ManifestError> {
+        if manifest.manifest_version == LEGACY_MANIFEST_VERSION {
+            return Ok(ManifestFormat {
+                version: LEGACY_MANIFEST_VERSION,
+                hash_alg: HashAlg::Sha256,
+            });
+        }
+
+        let name = manifest.hash_alg.as_deref().unwrap_or_default();
+        HashAlg::from_name(name)
+            .map(ManifestFormat::new)
+            .ok_or_else(|| ManifestError::UnsupportedHashAlg(name.to_string()))
+    }
+
+    /// Format of an existing manifest file, if it can be read.
+    pub(crate) fn of_file(path: &Path) -> Option<Self> {
+        load_manifest(path).ok().and_then(|m| Self::of_manifest(&m).ok())
+    }
+
+    fn is_legacy(self) -> bool {
+        self.version == LEGACY_MANIFEST_VERSION
+    }
+}
+
+impl Default for ManifestFormat {
+    fn default() -> Self {
+        ManifestFormat::new(HashAlg::default())
+    }
 }
 
 /// File entry in the manifest.
-#[derive(Debug, Serialize)]
-struct FileEntry {
-    path: String,
-    sha256: String,
-    size: u64,
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FileEntry {
+    pub path: String,
+    /// Hex digest under the manifest's `hash_alg` (`sha256` in v2.0.0).
+    #[serde(alias = "sha256")]
+    pub hash: String,
+    pub size: u64,
     #[serde(rename = "type")]
-    file_type: String,
+    pub file_type: String,
 }
 
 /// Complete manifest structure.
-#[derive(Debug, Serialize)]
-struct Manifest {
-    baseline: String,
-    domain: String,
-    file_count: usize,
-    files: Vec<FileEntry>,
-    generated_at: String,
-    generator: String,
-    manifest_version: String,
-}
-
-/// Compute SHA-256 hash of a file using chunked reading.
-fn compute_file_sha256(path: &Path) -> Result<String, ManifestError> {
-    let file = File::open(path)?;
-    let mut reader = BufReader::new(file);
-    let mut buffer = [0u8; 8192];
-    let mut hasher = Sha256::new();
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct Manifest {
+    pub baseline: String,
+    pub domain: String,
+    pub file_count: usize,
+    pub files: Vec<FileEntry>,
+    pub generated_at: String,
+    pub generator: String,
+    /// Absent in v2.0.0 manifests, which are always SHA-256.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash_alg: Option<String>,
+    pub manifest_version: String,
+    /// Merkle root over the file hashes (v3.0.0 and later).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub merkle_root: Option<String>,
+}
 
-    loop {
-        let bytes_read = reader.read(&mut buffer)?;
-        if bytes_read == 0 {
-            break;
+impl Manifest {
+    /// Merkle root over this manifest's file hashes.
+    pub(crate) fn computed_merkle_root(&self) -> Result<String, ManifestError> {
+        let hashes: Vec<&str> = self.files.iter().map(|f| f.hash.as_str()).collect();
+        Ok(merkle::root(&hashes)?)
+    }
+}
+
+/// Load a manifest from disk.
+pub(crate) fn load_manifest(path: &Path) -> Result<Manifest, ManifestError> {
+    let content = fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+/// Directory holding generated manifests.
+pub(crate) fn manifests_dir(repo_root: &Path) -> PathBuf {
+    repo_root.join("specs").join("_governance").join("manifests")
+}
+
+/// File name stem of the governance manifest.
+pub(crate) const GOVERNANCE_MANIFEST_STEM: &str = "MANIFEST_governance";
+
+/// File name stem of the manifest for a unit.
+pub(crate) fn unit_manifest_stem(unit_id: &str) -> String {
+    format!("UNIT_MANIFEST_{}", unit_id.replace('/', "_"))
+}
+
+/// File name of a manifest, suffixed with its hash algorithm.
+pub(crate) fn manifest_file_name(stem: &str, hash_alg: HashAlg) -> String {
+    format!("{}.{}.json", stem, hash_alg.file_suffix())
+}
+
+/// Existing manifest for a stem, whatever its hash algorithm.
+pub(crate) fn find_manifest(dir: &Path, stem: &str) -> Option<PathBuf> {
+    [HashAlg::Sha3_512, HashAlg::Sha256]
+        .into_iter()
+        .map(|alg| dir.join(manifest_file_name(stem, alg)))
+        .find(|path| path.exists())
+}
+
+/// Where to write a manifest and in which format.
+///
+/// An existing manifest keeps its format unless `hash_alg` is given, so its
+/// hash only changes with content; new manifests use the default format.
+/// Returns the output path, the format and the existing file, if any.
+pub(crate) fn resolve_manifest(
+    dir: &Path,
+    stem: &str,
+    hash_alg: Option<HashAlg>,
+) -> (PathBuf, ManifestFormat, Option<PathBuf>) {
+    let existing = find_manifest(dir, stem);
+    let format = match hash_alg {
+        Some(alg) => ManifestFormat::new(alg),
+        None => existing.as_deref().and_then(ManifestFormat::of_file).unwrap_or_default(),
+    };
+    (dir.join(manifest_file_name(stem, format.hash_alg)), format, existing)
+}
+
+/// Render a manifest in its committed form.
+fn render_manifest(manifest: &Manifest) -> Result<String, ManifestError> {
+    let mut value = serde_json::to_value(manifest)?;
+
+    // v2.0.0 names the per-file hash `sha256`
+    if manifest.manifest_version == LEGACY_MANIFEST_VERSION
+        && let Some(files) = value["files"].as_array_mut()
+    {
+        for file in files.iter_mut().filter_map(|f| f.as_object_mut()) {
+            if let Some(hash) = file.remove("hash") {
+                let _ = file.insert("sha256".to_string(), hash);
+            }
         }
-        hasher.update(&buffer[..bytes_read]);
     }
 
-    let result = hasher.finalize();
-    Ok(format!("{:x}", result))
+    let json = serde_json::to_string_pretty(&value)?;
+    Ok(format!("{}\n", json))
+}
+
+/// Format of `generated_at` timestamps.
+const GENERATED_AT_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.6f+00:00";
+
+/// Resolve a pinned `generated_at` from `--timestamp` or `SOURCE_DATE_EPOCH`.
+///
+/// `--timestamp` (RFC 3339) takes precedence over `SOURCE_DATE_EPOCH`
+/// (seconds since the Unix epoch). Returns `None` when neither is set.
+pub(crate) fn pinned_timestamp(timestamp: Option<&str>) -> Result<Option<String>, ManifestError> {
+    let pinned: DateTime<Utc> = match timestamp {
+        Some(ts) => DateTime::parse_from_rfc3339(ts)
+            .map_err(|e| ManifestError::InvalidTimestamp(format!("--timestamp {}: {}", ts, e)))?
+            .with_timezone(&Utc),
+        None => match std::env::var("SOURCE_DATE_EPOCH") {
+            Ok(epoch) => epoch
+                .trim()
+                .parse::<i64>()
+                .ok()
+                .and_then(|secs| DateTime::from_timestamp(secs, 0))
+                .ok_or_else(|| ManifestError::InvalidTimestamp(format!("SOURCE_DATE_EPOCH={}", epoch)))?,
+            Err(_) => return Ok(None),
+        },
+    };
+
+    Ok(Some(pinned.format(GENERATED_AT_FORMAT).to_string()))
+}
+
+/// Current time as a `generated_at` timestamp, truncated to whole seconds.
+pub(crate) fn now_timestamp() -> String {
+    let now = Utc::now();
+    DateTime::from_timestamp(now.timestamp(), 0)
+        .unwrap_or(now)
+        .format(GENERATED_AT_FORMAT)
+        .to_string()
 }
 
-/// Check if a file should be excluded from the manifest.
-fn should_exclude(path: &Path, is_governance: bool) -> bool {
-    let path_str = path.to_string_lossy();
+/// `generated_at` of an existing manifest file, if it can be read.
+fn existing_generated_at(path: &Path) -> Option<String> {
+    load_manifest(path).ok().map(|m| m.generated_at)
+}
 
-    // Exclude .git artifacts
-    if path_str.contains(".git") {
-        return true;
+/// Content a manifest file will have after a refresh, and whether it changes.
+///
+/// Without a pinned timestamp, the existing file is kept when only
+/// `generated_at` would change, which keeps manifest hashes stable across
+/// regenerations.
+pub(crate) fn refreshed_content(
+    path: &Path,
+    manifest: &mut Manifest,
+    pinned: bool,
+) -> Result<(String, bool), ManifestError> {
+    let existing = if path.exists() { Some(fs::read_to_string(path)?) } else { None };
+
+    if !pinned && let Some(generated_at) = existing_generated_at(path) {
+        let fresh = std::mem::replace(&mut manifest.generated_at, generated_at);
+        let kept = render_manifest(manifest)?;
+        if existing.as_deref() == Some(kept.as_str()) {
+            return Ok((kept, false));
+        }
+        manifest.generated_at = fresh;
     }
 
-    // Exclude _reference/ directories
-    if path_str.contains("_reference/") || path_str.contains("_reference\\") {
-        return true;
+    let new_content = render_manifest(manifest)?;
+    let changed = existing.as_deref() != Some(new_content.as_str());
+    Ok((new_content, changed))
+}
+
+/// Compute the hash of a file using chunked reading.
+pub(crate) fn compute_file_hash(path: &Path, hash_alg: HashAlg) -> Result<String, ManifestError> {
+    match hash_alg {
+        HashAlg::Sha256 => compute_file_digest::<Sha256>(path),
+        HashAlg::Sha3_512 => compute_file_digest::<Sha3_512>(path),
     }
+}
+
+fn compute_file_digest<D: Digest>(path: &Path) -> Result<String, ManifestError> {
+    let file = File::open(path)?;
+    let mut reader = BufReader::new(file);
+    let mut buffer = [0u8; 8192];
+    let mut hasher = D::new();
 
-    // Exclude common non-content files
-    if let Some(name) = path.file_name() {
-        let name_str = name.to_string_lossy();
-        if name_str == ".gitignore" || name_str == ".gitattributes" || name_str == "CODEOWNERS" {
-            return true;
+    loop {
+        let bytes_read = reader.read(&mut buffer)?;
+        if bytes_read == 0 {
+            break;
         }
+        hasher.update(&buffer[..bytes_read]);
     }
 
-    // For governance manifest: exclude the manifests/ subdirectory to avoid self-reference
-    if is_governance && (path_str.contains("manifests/") || path_str.contains("manifests\\")) {
-        return true;
-    }
+    let result = hasher.finalize();
+    Ok(hex::encode(result))
+}
 
-    false
+/// Collect the files a manifest of `target_path` covers.
+fn collect_files(target_path: &Path, is_governance: bool) -> Vec<PathBuf> {
+    WalkDir::new(target_path)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .map(|e| e.into_path())
+        .filter(|path| {
+            // Exclude the manifests subdirectory
+            let path_str = path.to_string_lossy();
+            if path_str.contains("manifests/") || path_str.contains("manifests\\") {
+                return false;
+            }
+            path.is_file() && !common::should_exclude_from_manifest(path, is_governance)
+        })
+        .collect()
 }
 
 /// Get file info for a single file.
-fn get_file_info(filepath: &Path, base_path: &Path) -> Result<FileEntry, ManifestError> {
+fn get_file_info(filepath: &Path, base_path: &Path, hash_alg: HashAlg) -> Result<FileEntry, ManifestError> {
     let metadata = fs::metadata(filepath)?;
     let relative = filepath
         .strip_prefix(base_path)
@@ -115,14 +392,27 @@ fn get_file_info(filepath: &Path, base_path: &Path) -> Result<FileEntry, Manifes
 
     Ok(FileEntry {
         path: relative,
-        sha256: compute_file_sha256(filepath)?,
+        hash: compute_file_hash(filepath, hash_alg)?,
         size: metadata.len(),
         file_type: "file".to_string(),
     })
 }
 
+/// Merkle root to record for `files`; legacy v2.0.0 manifests have none.
+fn format_merkle_root(files: &[FileEntry], format: ManifestFormat) -> Result<Option<String>, ManifestError> {
+    if format.is_legacy() {
+        return Ok(None);
+    }
+    let hashes: Vec<&str> = files.iter().map(|f| f.hash.as_str()).collect();
+    Ok(Some(merkle::root(&hashes)?))
+}
+
 /// Generate manifest for governance.
-fn generate_governance_manifest(repo_root: &Path) -> Result<Manifest, ManifestError> {
+fn generate_governance_manifest(
+    repo_root: &Path,
+    generated_at: &str,
+    format: ManifestFormat,
+) -> Result<Manifest, ManifestError> {
     let target_path = repo_root.join("specs").join("_governance");
 
     if !target_path.exists() {
@@ -131,60 +421,44 @@ fn generate_governance_manifest(repo_root: &Path) -> Result<Manifest, ManifestEr
 
     let mut files: Vec<FileEntry> = Vec::new();
 
-    for entry in WalkDir::new(&target_path)
-        .sort_by_file_name()
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
-        let path = entry.path();
-        if path.is_file() && !should_exclude(path, true) {
-            files.push(get_file_info(path, repo_root)?);
-        }
+    for path in collect_files(&target_path, true) {
+        files.push(get_file_info(&path, repo_root, format.hash_alg)?);
     }
 
     files.sort_by(|a, b| a.path.cmp(&b.path));
 
-    let now: DateTime<Utc> = Utc::now();
-
     Ok(Manifest {
-        manifest_version: "2.0.0".to_string(),
+        manifest_version: format.version.to_string(),
         domain: "_governance".to_string(),
         baseline: "global".to_string(),
-        generated_at: now.format("%Y-%m-%dT%H:%M:%S%.6f+00:00").to_string(),
+        generated_at: generated_at.to_string(),
         generator: "tools/xonaix-library-tools".to_string(),
+        hash_alg: (!format.is_legacy()).then(|| format.hash_alg.name().to_string()),
+        merkle_root: format_merkle_root(&files, format)?,
         file_count: files.len(),
         files,
     })
 }
 
 /// Generate manifest for a unit.
-fn generate_unit_manifest(unit_path: &Path, repo_root: &Path) -> Result<Manifest, ManifestError> {
+pub(crate) fn generate_unit_manifest(
+    unit_path: &Path,
+    repo_root: &Path,
+    generated_at: &str,
+    format: ManifestFormat,
+) -> Result<Manifest, ManifestError> {
     if !unit_path.exists() {
         return Err(ManifestError::PathNotFound(unit_path.to_path_buf()));
     }
 
     let mut files: Vec<FileEntry> = Vec::new();
 
-    for entry in WalkDir::new(unit_path)
-        .sort_by_file_name()
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
-        let path = entry.path();
-        // Exclude the manifests subdirectory
-        let path_str = path.to_string_lossy();
-        if path_str.contains("manifests/") || path_str.contains("manifests\\") {
-            continue;
-        }
-        if path.is_file() && !should_exclude(path, false) {
-            files.push(get_file_info(path, repo_root)?);
-        }
+    for path in collect_files(unit_path, false) {
+        files.push(get_file_info(&path, repo_root, format.hash_alg)?);
     }
 
     files.sort_by(|a, b| a.path.cmp(&b.path));
 
-    let now: DateTime<Utc> = Utc::now();
-
     // Extract unit_id from path
     let relative = unit_path
         .strip_prefix(repo_root)
@@ -193,41 +467,86 @@ fn generate_unit_manifest(unit_path: &Path, repo_root: &Path) -> Result<Manifest
         .replace('\\', "/");
 
     Ok(Manifest {
-        manifest_version: "2.0.0".to_string(),
+        manifest_version: format.version.to_string(),
         domain: "unit".to_string(),
         baseline: relative,
-        generated_at: now.format("%Y-%m-%dT%H:%M:%S%.6f+00:00").to_string(),
+        generated_at: generated_at.to_string(),
         generator: "tools/xonaix-library-tools".to_string(),
+        hash_alg: (!format.is_legacy()).then(|| format.hash_alg.name().to_string()),
+        merkle_root: format_merkle_root(&files, format)?,
         file_count: files.len(),
         files,
     })
 }
 
-/// Find repository root by looking for specs/ directory.
+/// Find repository root, reporting the starting directory if there is none.
 fn find_repo_root() -> Result<PathBuf, ManifestError> {
-    let mut current = std::env::current_dir()?;
+    match common::find_repo_root()? {
+        Some(root) => Ok(root),
+        None => Err(ManifestError::NoSpecsDir(std::env::current_dir()?)),
+    }
+}
 
-    if current.join("specs").exists() {
-        return Ok(current);
+/// Read unit_id -> path for every unit in the registry.
+fn load_registry_paths(repo_root: &Path) -> Result<BTreeMap<String, String>, ManifestError> {
+    let registry_path = repo_root.join("specs").join("_governance").join("UNIT_REGISTRY.json");
+    let registry_content = fs::read_to_string(&registry_path)?;
+    let registry: serde_json::Value = serde_json::from_str(&registry_content)?;
+
+    let units = registry["units"]
+        .as_object()
+        .ok_or_else(|| ManifestError::InvalidArgs("Registry has no units".to_string()))?;
+
+    let mut paths = BTreeMap::new();
+    for (unit_id, entry) in units {
+        let unit_path = entry["path"]
+            .as_str()
+            .ok_or_else(|| ManifestError::InvalidArgs(format!("Unit has no path: {}", unit_id)))?;
+        let _ = paths.insert(unit_id.clone(), unit_path.to_string());
     }
 
-    while let Some(parent) = current.parent() {
-        if parent.join("specs").exists() {
-            return Ok(parent.to_path_buf());
-        }
-        current = parent.to_path_buf();
+    Ok(paths)
+}
+
+/// What a manifest covers.
+enum TargetSource {
+    Governance,
+    Unit(PathBuf),
+}
+
+/// A manifest to write or check, with its default file name stem.
+struct ManifestTarget {
+    label: String,
+    source: TargetSource,
+    stem: String,
+}
+
+/// Compare a regenerated manifest with the file on disk, byte for byte.
+///
+/// Returns a description of the drift, or `None` if up-to-date.
+fn check_manifest(output_path: &Path, new_content: &str) -> Result<Option<String>, ManifestError> {
+    if !output_path.exists() {
+        return Ok(Some(format!("Manifest file does not exist: {}", output_path.display())));
     }
 
-    Err(ManifestError::NoSpecsDir(std::env::current_dir()?))
+    if fs::read_to_string(output_path)? != new_content {
+        return Ok(Some(output_path.display().to_string()));
+    }
+
+    Ok(None)
 }
 
 /// Main entry point for manifest generation.
+#[allow(clippy::too_many_arguments)]
 pub fn run(
     governance: bool,
     unit: Option<String>,
+    all_units: bool,
     output: Option<String>,
     repo_root_arg: Option<String>,
     check: bool,
+    timestamp: Option<String>,
+    hash_alg: Option<String>,
 ) -> Result<(), ManifestError> {
     let repo_root = match repo_root_arg {
         Some(path) => PathBuf::from(path),
@@ -238,83 +557,441 @@ pub fn run(
         return Err(ManifestError::NoSpecsDir(repo_root));
     }
 
-    let (manifest, output_name) = if governance {
-        let m = generate_governance_manifest(&repo_root)?;
-        (m, "MANIFEST_governance.sha256.json".to_string())
-    } else if let Some(unit_id) = unit {
-        // Find unit path from registry
-        let registry_path = repo_root.join("specs").join("_governance").join("UNIT_REGISTRY.json");
-        let registry_content = fs::read_to_string(&registry_path)?;
-        let registry: serde_json::Value = serde_json::from_str(&registry_content)?;
-
-        let unit_entry = registry["units"][&unit_id].as_object()
-            .ok_or_else(|| ManifestError::InvalidArgs(format!("Unit not found: {}", unit_id)))?;
-
-        let unit_path_str = unit_entry["path"].as_str()
-            .ok_or_else(|| ManifestError::InvalidArgs("Unit has no path".to_string()))?;
+    // Without a pinned timestamp, the existing file's generated_at is kept
+    // unless the content changes, in both check and write mode.
+    let pinned = pinned_timestamp(timestamp.as_deref())?;
+    let generated_at = pinned.clone().unwrap_or_else(now_timestamp);
+    let hash_alg = hash_alg.map(|a| a.parse::<HashAlg>()).transpose()?;
+
+    let mut targets = Vec::new();
+    if governance {
+        targets.push(ManifestTarget {
+            label: "_governance".to_string(),
+            source: TargetSource::Governance,
+            stem: GOVERNANCE_MANIFEST_STEM.to_string(),
+        });
+    }
+    if all_units || unit.is_some() {
+        // Find unit paths from registry
+        let registry = load_registry_paths(&repo_root)?;
+        let unit_ids: Vec<String> = match unit {
+            Some(unit_id) => {
+                if !registry.contains_key(&unit_id) {
+                    return Err(ManifestError::InvalidArgs(format!("Unit not found: {}", unit_id)));
+                }
+                vec![unit_id]
+            }
+            None => registry.keys().cloned().collect(),
+        };
+
+        for unit_id in unit_ids {
+            targets.push(ManifestTarget {
+                source: TargetSource::Unit(repo_root.join(&registry[&unit_id])),
+                stem: unit_manifest_stem(&unit_id),
+                label: unit_id,
+            });
+        }
+    }
 
-        let unit_path = repo_root.join(unit_path_str);
-        let m = generate_unit_manifest(&unit_path, &repo_root)?;
-        let safe_name = unit_id.replace('/', "_");
-        (m, format!("UNIT_MANIFEST_{}.sha256.json", safe_name))
-    } else {
+    if targets.is_empty() {
         return Err(ManifestError::InvalidArgs(
-            "Either --governance or --unit is required".to_string(),
+            "One of --governance, --unit or --all-units is required".to_string(),
         ));
-    };
+    }
+    if output.is_some() && targets.len() > 1 {
+        return Err(ManifestError::InvalidArgs(
+            "--output requires a single manifest".to_string(),
+        ));
+    }
 
-    let output_path = match output {
-        Some(path) => PathBuf::from(path),
-        None => {
-            let manifests_dir = repo_root.join("specs").join("_governance").join("manifests");
-            if !manifests_dir.exists() {
-                fs::create_dir_all(&manifests_dir)?;
+    let manifests_dir = manifests_dir(&repo_root);
+    let mut drifted: Vec<(String, String)> = Vec::new();
+    let mut failed: Vec<(String, String)> = Vec::new();
+
+    for target in &targets {
+        let (output_path, format, existing) = match &output {
+            Some(path) => {
+                let path = PathBuf::from(path);
+                let format = match hash_alg {
+                    Some(alg) => ManifestFormat::new(alg),
+                    None => ManifestFormat::of_file(&path).unwrap_or_default(),
+                };
+                (path, format, None)
+            }
+            None => {
+                if !check && !manifests_dir.exists() {
+                    fs::create_dir_all(&manifests_dir)?;
+                }
+                resolve_manifest(&manifests_dir, &target.stem, hash_alg)
+            }
+        };
+        // A manifest under another algorithm's name is replaced, not kept alongside
+        let previous = existing.unwrap_or_else(|| output_path.clone());
+        let superseded = (previous != output_path).then_some(previous.as_path());
+
+        let mut manifest = match &target.source {
+            TargetSource::Governance => generate_governance_manifest(&repo_root, &generated_at, format)?,
+            TargetSource::Unit(unit_path) => match generate_unit_manifest(unit_path, &repo_root, &generated_at, format) {
+                Ok(manifest) => manifest,
+                // One missing unit must not hide the state of the others
+                Err(e @ ManifestError::PathNotFound(_)) if targets.len() > 1 => {
+                    println!("FAIL: {}: {}", target.label, e);
+                    failed.push((target.label.clone(), e.to_string()));
+                    continue;
+                }
+                Err(e) => return Err(e),
+            },
+        };
+
+        let (new_content, changed) = refreshed_content(&previous, &mut manifest, pinned.is_some())?;
+
+        if check {
+            match check_manifest(&output_path, &new_content)? {
+                Some(reason) => {
+                    println!("FAIL: Manifest drift detected!");
+                    println!("Path: {}", output_path.display());
+                    drifted.push((target.label.clone(), reason));
+                }
+                None => {
+                    println!("PASS: Manifest up-to-date: {}", output_path.display());
+                    println!("Files: {}", manifest.file_count);
+                }
             }
-            manifests_dir.join(&output_name)
+            continue;
         }
-    };
 
-    let json = serde_json::to_string_pretty(&manifest)?;
-    let new_content = format!("{}\n", json);
+        if !changed && superseded.is_none() {
+            println!("Unchanged: {}", output_path.display());
+            println!("Files: {}", manifest.file_count);
+            continue;
+        }
+
+        let mut file = File::create(&output_path)?;
+        file.write_all(new_content.as_bytes())?;
+        if let Some(old_path) = superseded {
+            fs::remove_file(old_path)?;
+            println!("Removed: {}", old_path.display());
+        }
+
+        println!("Generated: {}", output_path.display());
+        println!("Files: {}", manifest.file_count);
+    }
+
+    if targets.len() > 1 {
+        println!();
+        println!("=== MANIFEST SUMMARY ===");
+        println!("Manifests: {}", targets.len());
+        if check {
+            println!("Up-to-date: {}", targets.len() - drifted.len() - failed.len());
+            println!("Drifted: {}", drifted.len());
+            for (label, _) in &drifted {
+                println!("  - {}", label);
+            }
+        }
+        println!("Failed: {}", failed.len());
+        for (label, reason) in &failed {
+            println!("  - {}: {}", label, reason);
+        }
+    }
+
+    if !failed.is_empty() {
+        return Err(ManifestError::GenerationFailed(failed.len(), targets.len()));
+    }
 
-    if check {
-        if !output_path.exists() {
+    match drifted.len() {
+        0 => {}
+        1 => return Err(ManifestError::ManifestDrift(drifted.remove(0).1)),
+        n => {
             return Err(ManifestError::ManifestDrift(format!(
-                "Manifest file does not exist: {}",
-                output_path.display()
-            )));
+                "{} of {} manifests",
+                n,
+                targets.len()
+            )))
         }
+    }
+
+    Ok(())
+}
+
+/// Resolve a repository-relative path recorded in a manifest or BOM.
+///
+/// Absolute paths and `..` components are rejected, so a manifest cannot
+/// point verification at files outside the repository.
+pub(crate) fn repo_path(repo_root: &Path, relative: &str) -> Result<PathBuf, String> {
+    let path = Path::new(relative);
+    let escapes = relative.is_empty()
+        || relative.split(['/', '\\']).any(|part| part == "..")
+        || !path.components().all(|c| matches!(c, Component::Normal(_) | Component::CurDir));
+    if escapes {
+        return Err(format!("path is not inside the repository: '{}'", relative));
+    }
+    Ok(repo_root.join(path))
+}
 
-        let existing_content = fs::read_to_string(&output_path)?;
+/// Directory a manifest covers, derived from its domain and baseline.
+///
+/// Returns `Ok(None)` for an unknown domain.
+fn manifest_scope(manifest: &Manifest, repo_root: &Path) -> Result<Option<(PathBuf, bool)>, String> {
+    match manifest.domain.as_str() {
+        "_governance" => Ok(Some((repo_root.join("specs").join("_governance"), true))),
+        "unit" => Ok(Some((repo_path(repo_root, &manifest.baseline)?, false))),
+        _ => Ok(None),
+    }
+}
+
+/// Run the `manifest verify` command.
+///
+/// Rehashes every file listed in an existing manifest and reports each
+/// discrepancy per file, without regenerating the manifest.
+pub fn verify(manifest_file: String, repo_root_arg: Option<String>) -> Result<(), ManifestError> {
+    let repo_root = match repo_root_arg {
+        Some(path) => PathBuf::from(path),
+        None => find_repo_root()?,
+    };
 
-        fn strip_generated_at(s: &str) -> String {
-            s.lines()
-                .filter(|line| !line.trim_start().starts_with("\"generated_at\""))
-                .collect::<Vec<_>>()
-                .join("\n")
+    let manifest_path = PathBuf::from(&manifest_file);
+    if !manifest_path.exists() {
+        return Err(ManifestError::PathNotFound(manifest_path));
+    }
+    let manifest = load_manifest(&manifest_path)?;
+    // Any formatting is accepted; the hash is taken over the canonical form.
+    let manifest_hash = canonical::canonical_hash(&fs::read_to_string(&manifest_path)?)?;
+    let format = ManifestFormat::of_manifest(&manifest)?;
+
+    println!("=== XONAIX MANIFEST VERIFICATION ===");
+    println!("Repository: {}", repo_root.display());
+    println!("Manifest: {}", manifest_path.display());
+    println!("Manifest Hash (SHA3-512, canonical): {}", manifest_hash);
+    println!("Domain: {} ({})", manifest.domain, manifest.baseline);
+    println!("Format: v{} ({})", format.version, format.hash_alg.name());
+    println!("Files: {}", manifest.files.len());
+    println!();
+
+    let mut ok = 0usize;
+    let mut failed = 0usize;
+
+    if let Some(recorded) = &manifest.merkle_root
+        && !recorded.eq_ignore_ascii_case(&manifest.computed_merkle_root()?)
+    {
+        println!("FAIL: merkle_root does not match the listed file hashes");
+        failed += 1;
+    }
+
+    if manifest.file_count != manifest.files.len() {
+        println!(
+            "FAIL: file_count is {} but {} files are listed",
+            manifest.file_count,
+            manifest.files.len()
+        );
+        failed += 1;
+    }
+
+    for entry in &manifest.files {
+        let path = match repo_path(&repo_root, &entry.path) {
+            Ok(path) => path,
+            Err(reason) => {
+                println!("FAIL: {}", reason);
+                failed += 1;
+                continue;
+            }
+        };
+        if !path.is_file() {
+            println!("MISSING: {}", entry.path);
+            failed += 1;
+            continue;
         }
 
-        let new_stripped = strip_generated_at(&new_content);
-        let existing_stripped = strip_generated_at(&existing_content);
+        let mut problems = Vec::new();
+        match fs::metadata(&path) {
+            Ok(meta) if meta.len() != entry.size => {
+                problems.push(format!("size mismatch (expected {}, found {})", entry.size, meta.len()));
+            }
+            Ok(_) => {}
+            Err(e) => problems.push(e.to_string()),
+        }
+        match compute_file_hash(&path, format.hash_alg) {
+            Ok(hash) if !hash.eq_ignore_ascii_case(&entry.hash) => problems.push("hash mismatch".to_string()),
+            Ok(_) => {}
+            Err(e) => problems.push(e.to_string()),
+        }
 
-        if new_stripped != existing_stripped {
-            println!("FAIL: Manifest drift detected!");
-            println!("Path: {}", output_path.display());
-            return Err(ManifestError::ManifestDrift(
-                output_path.display().to_string()
-            ));
+        if problems.is_empty() {
+            println!("OK: {}", entry.path);
+            ok += 1;
+        } else {
+            println!("FAIL: {}: {}", entry.path, problems.join(", "));
+            failed += 1;
         }
+    }
 
-        println!("PASS: Manifest up-to-date: {}", output_path.display());
-        println!("Files: {}", manifest.file_count);
-        return Ok(());
+    match manifest_scope(&manifest, &repo_root) {
+        Ok(Some((scope, is_governance))) => {
+            let listed: BTreeSet<&str> = manifest.files.iter().map(|f| f.path.as_str()).collect();
+            for path in collect_files(&scope, is_governance) {
+                let relative = path
+                    .strip_prefix(&repo_root)
+                    .unwrap_or(&path)
+                    .to_string_lossy()
+                    .replace('\\', "/");
+                if !listed.contains(relative.as_str()) {
+                    println!("EXTRA: {}", relative);
+                    failed += 1;
+                }
+            }
+        }
+        Ok(None) => println!("WARN: unknown manifest domain '{}', extra files not checked", manifest.domain),
+        Err(reason) => {
+            println!("FAIL: baseline {}", reason);
+            failed += 1;
+        }
     }
 
-    let mut file = File::create(&output_path)?;
-    file.write_all(new_content.as_bytes())?;
+    println!();
+    println!("=== MANIFEST VERIFICATION COMPLETE ===");
+    println!("Verified: {}", ok);
+    println!("Discrepancies: {}", failed);
 
-    println!("Generated: {}", output_path.display());
-    println!("Files: {}", manifest.file_count);
+    if failed > 0 {
+        Err(ManifestError::VerificationFailed(failed))
+    } else {
+        println!("Result: PASS");
+        Ok(())
+    }
+}
 
-    Ok(())
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repo_path_accepts_relative_paths() {
+        let root = Path::new("/repo");
+        assert_eq!(repo_path(root, "specs/a.md"), Ok(PathBuf::from("/repo/specs/a.md")));
+        assert_eq!(repo_path(root, "./specs/a.md"), Ok(PathBuf::from("/repo/specs/a.md")));
+    }
+
+    #[test]
+    fn repo_path_rejects_escaping_paths() {
+        let root = Path::new("/repo");
+        for path in ["", "/etc/passwd", "../secret", "specs/../../secret", "specs\\..\\..\\secret"] {
+            assert!(repo_path(root, path).is_err(), "{} accepted", path);
+        }
+    }
+
+    fn test_manifest(generated_at: &str, hash: &str) -> Manifest {
+        Manifest {
+            baseline: "global".to_string(),
+            domain: "_governance".to_string(),
+            file_count: 1,
+            files: vec![FileEntry {
+                path: "specs/_governance/a.md".to_string(),
+                hash: hash.to_string(),
+                size: 1,
+                file_type: "markdown".to_string(),
+            }],
+            generated_at: generated_at.to_string(),
+            generator: "test".to_string(),
+            hash_alg: Some("sha3-512".to_string()),
+            manifest_version: MANIFEST_VERSION.to_string(),
+            merkle_root: None,
+        }
+    }
+
+    #[test]
+    fn refresh_keeps_generated_at_unless_content_changes() {
+        let path = std::env::temp_dir().join(format!("xlib-manifest-refresh-{}.json", std::process::id()));
+        let old = "2025-01-01T00:00:00.000000+00:00";
+        let new = "2026-01-01T00:00:00.000000+00:00";
+        let written = render_manifest(&test_manifest(old, "aa")).unwrap_or_default();
+        assert!(fs::write(&path, &written).is_ok());
+
+        let mut same = test_manifest(new, "aa");
+        let unchanged = refreshed_content(&path, &mut same, false);
+        assert!(matches!(&unchanged, Ok((content, false)) if *content == written));
+
+        let mut pinned = test_manifest(new, "aa");
+        let repinned = refreshed_content(&path, &mut pinned, true);
+        assert!(matches!(&repinned, Ok((content, true)) if content.contains(new)));
+
+        let mut edited = test_manifest(new, "bb");
+        let changed = refreshed_content(&path, &mut edited, false);
+        assert!(matches!(&changed, Ok((content, true)) if content.contains(new)));
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn all_units_reports_missing_unit_and_finishes_the_rest() {
+        let root = std::env::temp_dir().join(format!("xlib-manifest-all-units-{}", std::process::id()));
+        let governance = root.join("specs").join("_governance");
+        assert!(fs::create_dir_all(&governance).is_ok());
+        assert!(fs::create_dir_all(root.join("specs").join("present")).is_ok());
+        assert!(fs::write(root.join("specs").join("present").join("DOC.md"), "# Doc\n").is_ok());
+        let registry = r#"{"units": {"test/missing": {"path": "specs/missing"}, "test/present": {"path": "specs/present"}}}"#;
+        assert!(fs::write(governance.join("UNIT_REGISTRY.json"), registry).is_ok());
+
+        let result = run(
+            false,
+            None,
+            true,
+            None,
+            Some(root.to_string_lossy().into_owned()),
+            false,
+            Some("2025-01-01T00:00:00Z".to_string()),
+            None,
+        );
+        assert!(matches!(result, Err(ManifestError::GenerationFailed(1, 2))));
+        assert!(find_manifest(&manifests_dir(&root), &unit_manifest_stem("test/present")).is_some());
+        assert!(find_manifest(&manifests_dir(&root), &unit_manifest_stem("test/missing")).is_none());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn manifest_files_are_named_by_hash_alg_and_keep_their_format() {
+        let root = std::env::temp_dir().join(format!("xlib-manifest-naming-{}", std::process::id()));
+        let governance = root.join("specs").join("_governance");
+        assert!(fs::create_dir_all(&governance).is_ok());
+        assert!(fs::create_dir_all(root.join("specs").join("unit")).is_ok());
+        assert!(fs::write(root.join("specs").join("unit").join("DOC.md"), "# Doc\n").is_ok());
+        let registry = r#"{"units": {"test/unit": {"path": "specs/unit"}}}"#;
+        assert!(fs::write(governance.join("UNIT_REGISTRY.json"), registry).is_ok());
+
+        let dir = manifests_dir(&root);
+        let stem = unit_manifest_stem("test/unit");
+        let sha256 = dir.join(manifest_file_name(&stem, HashAlg::Sha256));
+        let sha3 = dir.join(manifest_file_name(&stem, HashAlg::Sha3_512));
+        let generate = |hash_alg: Option<&str>| {
+            run(
+                false,
+                Some("test/unit".to_string()),
+                false,
+                None,
+                Some(root.to_string_lossy().into_owned()),
+                false,
+                None,
+                hash_alg.map(str::to_string),
+            )
+        };
+
+        assert!(generate(Some("sha256")).is_ok());
+        assert!(sha256.exists() && !sha3.exists());
+
+        // Without --hash-alg the existing format is kept
+        assert!(generate(None).is_ok());
+        assert!(sha256.exists() && !sha3.exists());
+        assert_eq!(ManifestFormat::of_file(&sha256), Some(ManifestFormat::new(HashAlg::Sha256)));
+
+        // Switching algorithm renames the file
+        assert!(generate(Some("sha3-512")).is_ok());
+        assert!(sha3.exists() && !sha256.exists());
+        assert_eq!(ManifestFormat::of_file(&sha3), Some(ManifestFormat::new(HashAlg::Sha3_512)));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn now_timestamp_has_whole_seconds() {
+        assert!(now_timestamp().ends_with(".000000+00:00"));
+    }
 }
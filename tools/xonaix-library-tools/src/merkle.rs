@@ -0,0 +1,401 @@
+//! Merkle tree module.
+//!
+//! Builds SHA3-512 Merkle trees over manifest file hashes and BOM unit roots,
+//! and produces and checks inclusion proofs as described in
+//! ZEROPOINT_INTEGRATION_SPEC §4.2.
+//!
+//! Leaves are hashed as `SHA3-512(0x00 || file_hash)` and interior nodes as
+//! `SHA3-512(0x01 || min(a, b) || max(a, b))`. Nodes are sorted before the
+//! tree is built and each pair is hashed in sorted order, so a proof is a
+//! plain list of sibling hashes with no left/right flags. An odd node at the
+//! end of a level is promoted unchanged.
+//!
+//! A BOM tree is built over the unit manifest roots directly, without leaf
+//! hashing, so a file proof followed by its unit proof walks from the file
+//! hash up to the BOM root.
+
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_512};
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+use crate::bom::{self, BomError};
+use crate::common::{find_repo_root, resolved_relative_path};
+use crate::manifest::{self, HashAlg, ManifestError, ManifestFormat};
+use crate::unit::{self, UnitError};
+
+/// Domain separation prefix for leaf hashes.
+const LEAF_PREFIX: u8 = 0x00;
+
+/// Domain separation prefix for interior node hashes.
+const NODE_PREFIX: u8 = 0x01;
+
+/// Hash algorithm of Merkle nodes.
+pub(crate) const MERKLE_HASH_ALG: &str = "SHA3-512";
+
+/// Errors during Merkle operations.
+#[derive(Debug, Error)]
+pub enum MerkleError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error(transparent)]
+    Manifest(#[from] ManifestError),
+
+    #[error(transparent)]
+    Bom(#[from] BomError),
+
+    #[error(transparent)]
+    Unit(#[from] UnitError),
+
+    #[error("Cannot find specs/ directory")]
+    NoSpecsDir,
+
+    #[error(transparent)]
+    InvalidHash(#[from] InvalidHash),
+
+    #[error("File is not in any registered unit: {0}")]
+    NotInUnit(String),
+
+    #[error("Unit {0} has no manifest (run generate-manifest --unit {0})")]
+    NoManifest(String),
+
+    #[error("File is not listed in {0}: {1}")]
+    NotInManifest(String, String),
+
+    #[error("Unit {0} is not in {1}")]
+    NotInBom(String, String),
+
+    #[error("Unit manifest {0} does not match the hash recorded in {1}")]
+    ManifestChanged(String, String),
+
+    #[error("Proof does not verify against root {0}")]
+    ProofFailed(String),
+
+    #[error("File hash mismatch: {0} does not match the proof's file_hash")]
+    FileMismatch(String),
+}
+
+/// A node or file hash that is not valid hex.
+#[derive(Debug, Error)]
+#[error("Invalid hex hash: {0}")]
+pub struct InvalidHash(String);
+
+/// Inclusion proof for one file.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct InclusionProof {
+    /// Repo-relative path of the proven file.
+    pub file: String,
+    /// Hash of the file content, under `file_hash_alg`.
+    pub file_hash: String,
+    pub file_hash_alg: String,
+    /// Library release the proof reaches, if it extends to a BOM root.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub library_version: Option<String>,
+    /// Root the proof walks to: the BOM root, or the unit root.
+    pub merkle_root: String,
+    pub merkle_hash_alg: String,
+    /// Sibling hashes from the leaf up to `merkle_root`.
+    pub proof: Vec<String>,
+    pub unit_id: String,
+    pub unit_merkle_root: String,
+}
+
+fn decode(hash: &str) -> Result<Vec<u8>, InvalidHash> {
+    hex::decode(hash.trim()).map_err(|_| InvalidHash(hash.to_string()))
+}
+
+/// Hash a leaf: `SHA3-512(0x00 || data)`.
+fn leaf_hash(data: &[u8]) -> Vec<u8> {
+    let mut hasher = Sha3_512::new();
+    hasher.update([LEAF_PREFIX]);
+    hasher.update(data);
+    hasher.finalize().to_vec()
+}
+
+/// Hash two nodes in sorted order: `SHA3-512(0x01 || min || max)`.
+fn node_hash(a: &[u8], b: &[u8]) -> Vec<u8> {
+    let (lo, hi) = if a <= b { (a, b) } else { (b, a) };
+    let mut hasher = Sha3_512::new();
+    hasher.update([NODE_PREFIX]);
+    hasher.update(lo);
+    hasher.update(hi);
+    hasher.finalize().to_vec()
+}
+
+/// Hash one level into the next, promoting an odd last node.
+fn next_level(level: &[Vec<u8>]) -> Vec<Vec<u8>> {
+    level
+        .chunks(2)
+        .map(|pair| match pair {
+            [a, b] => node_hash(a, b),
+            _ => pair.concat(),
+        })
+        .collect()
+}
+
+/// Root over already-hashed nodes. An empty tree has the hash of no input.
+fn root_of_nodes(mut level: Vec<Vec<u8>>) -> String {
+    if level.is_empty() {
+        return hex::encode(Sha3_512::digest([]));
+    }
+
+    level.sort();
+    while level.len() > 1 {
+        level = next_level(&level);
+    }
+    hex::encode(&level[0])
+}
+
+/// Sibling path from `target` to the root of a tree over `level`.
+fn proof_of_nodes(mut level: Vec<Vec<u8>>, target: &[u8]) -> Option<Vec<String>> {
+    level.sort();
+    let mut idx = level.iter().position(|n| n == target)?;
+
+    let mut proof = Vec::new();
+    while level.len() > 1 {
+        if let Some(sibling) = level.get(idx ^ 1) {
+            proof.push(hex::encode(sibling));
+        }
+        idx /= 2;
+        level = next_level(&level);
+    }
+    Some(proof)
+}
+
+/// Merkle root over a set of file hashes (hex).
+pub(crate) fn root(file_hashes: &[&str]) -> Result<String, InvalidHash> {
+    let leaves = file_hashes
+        .iter()
+        .map(|h| decode(h).map(|b| leaf_hash(&b)))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(root_of_nodes(leaves))
+}
+
+/// Merkle root over a set of subtree roots (hex), without leaf hashing.
+pub(crate) fn root_of_roots(roots: &[&str]) -> Result<String, InvalidHash> {
+    let nodes = roots.iter().map(|r| decode(r)).collect::<Result<Vec<_>, _>>()?;
+    Ok(root_of_nodes(nodes))
+}
+
+/// Inclusion proof for `file_hash` in the tree over `file_hashes`.
+pub(crate) fn prove(file_hashes: &[&str], file_hash: &str) -> Result<Option<Vec<String>>, InvalidHash> {
+    let leaves = file_hashes
+        .iter()
+        .map(|h| decode(h).map(|b| leaf_hash(&b)))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(proof_of_nodes(leaves, &leaf_hash(&decode(file_hash)?)))
+}
+
+/// Inclusion proof for `unit_root` in the tree over `roots`.
+fn prove_root(roots: &[&str], unit_root: &str) -> Result<Option<Vec<String>>, InvalidHash> {
+    let nodes = roots.iter().map(|r| decode(r)).collect::<Result<Vec<_>, _>>()?;
+    Ok(proof_of_nodes(nodes, &decode(unit_root)?))
+}
+
+/// Walk a proof from a file hash and return the computed root.
+pub(crate) fn walk(file_hash: &str, proof: &[String]) -> Result<String, InvalidHash> {
+    let mut acc = leaf_hash(&decode(file_hash)?);
+    for sibling in proof {
+        acc = node_hash(&acc, &decode(sibling)?);
+    }
+    Ok(hex::encode(acc))
+}
+
+/// Run the `merkle prove` command.
+///
+/// Proves a file against its unit manifest root, and with a library version
+/// extends the proof to that release's BOM root.
+pub fn prove_file(
+    repo_root_arg: Option<String>,
+    file: String,
+    library_version: Option<String>,
+    output: Option<String>,
+) -> Result<(), MerkleError> {
+    let repo_root = match repo_root_arg {
+        Some(path) => PathBuf::from(path),
+        None => find_repo_root()?.ok_or(MerkleError::NoSpecsDir)?,
+    };
+
+    let rel = resolved_relative_path(Path::new(&file), &repo_root);
+
+    // Locate the unit containing the file
+    let registry = unit::load_registry(&repo_root)?;
+    let unit_id = registry
+        .units
+        .iter()
+        .filter(|(_, e)| rel.starts_with(&format!("{}/", e.path.trim_end_matches('/'))))
+        .max_by_key(|(_, e)| e.path.len())
+        .map(|(id, _)| id.clone())
+        .ok_or_else(|| MerkleError::NotInUnit(rel.clone()))?;
+
+    let manifest_path = manifest::find_manifest(&manifest::manifests_dir(&repo_root), &manifest::unit_manifest_stem(&unit_id))
+        .ok_or_else(|| MerkleError::NoManifest(unit_id.clone()))?;
+    let unit_manifest = manifest::load_manifest(&manifest_path)?;
+    let format = ManifestFormat::of_manifest(&unit_manifest)?;
+
+    let entry = unit_manifest
+        .files
+        .iter()
+        .find(|f| f.path == rel)
+        .ok_or_else(|| MerkleError::NotInManifest(manifest_path.display().to_string(), rel.clone()))?;
+
+    let file_hashes: Vec<&str> = unit_manifest.files.iter().map(|f| f.hash.as_str()).collect();
+    let unit_root = root(&file_hashes)?;
+    let mut proof = prove(&file_hashes, &entry.hash)?
+        .ok_or_else(|| MerkleError::NotInManifest(manifest_path.display().to_string(), rel.clone()))?;
+    let mut merkle_root = unit_root.clone();
+
+    if let Some(version) = &library_version {
+        let library_bom = bom::load_bom(&bom::bom_path(&repo_root, version))?;
+        let bom_unit = library_bom
+            .units
+            .iter()
+            .find(|u| u.unit_id == unit_id)
+            .ok_or_else(|| MerkleError::NotInBom(unit_id.clone(), version.clone()))?;
+        if !bom::hash_file(&manifest_path)?.eq_ignore_ascii_case(&bom_unit.manifest_hash) {
+            return Err(MerkleError::ManifestChanged(manifest_path.display().to_string(), version.clone()));
+        }
+
+        let unit_roots: Vec<&str> = library_bom.units.iter().map(|u| u.merkle_root.as_str()).collect();
+        let unit_proof = prove_root(&unit_roots, &unit_root)?
+            .ok_or_else(|| MerkleError::NotInBom(unit_id.clone(), version.clone()))?;
+        proof.extend(unit_proof);
+        merkle_root = library_bom.merkle_root.clone();
+    }
+
+    let inclusion = InclusionProof {
+        file: rel,
+        file_hash: entry.hash.clone(),
+        file_hash_alg: format.hash_alg.name().to_string(),
+        library_version,
+        merkle_root,
+        merkle_hash_alg: MERKLE_HASH_ALG.to_string(),
+        proof,
+        unit_id,
+        unit_merkle_root: unit_root,
+    };
+    let json = format!("{}\n", serde_json::to_string_pretty(&inclusion)?);
+
+    match output {
+        Some(path) => {
+            fs::write(&path, json)?;
+            println!("Proof: {}", path);
+            println!("File: {}", inclusion.file);
+            println!("Root: {}", inclusion.merkle_root);
+            println!("Steps: {}", inclusion.proof.len());
+        }
+        None => print!("{}", json),
+    }
+
+    Ok(())
+}
+
+/// Run the `merkle verify` command.
+///
+/// Walks a proof to its root and compares it with the trusted root given on
+/// the command line. With a file, also checks the file content against the
+/// proof's `file_hash`.
+pub fn verify(proof_path: String, trusted_root: String, file: Option<String>) -> Result<(), MerkleError> {
+    let _ = decode(&trusted_root)?;
+    let trusted_root = trusted_root.trim().to_string();
+    let inclusion: InclusionProof = serde_json::from_str(&fs::read_to_string(&proof_path)?)?;
+
+    println!("=== XONAIX MERKLE PROOF VERIFICATION ===");
+    println!("Proof: {}", proof_path);
+    println!("File: {}", inclusion.file);
+    if let Some(version) = &inclusion.library_version {
+        println!("Library Version: {}", version);
+    }
+    println!("Trusted Root: {}...", &trusted_root[..trusted_root.len().min(16)]);
+    println!();
+
+    if let Some(path) = file {
+        let alg = HashAlg::from_name(&inclusion.file_hash_alg)
+            .ok_or_else(|| ManifestError::UnsupportedHashAlg(inclusion.file_hash_alg.clone()))?;
+        let actual = manifest::compute_file_hash(Path::new(&path), alg)?;
+        if !actual.eq_ignore_ascii_case(&inclusion.file_hash) {
+            println!("FAIL: {} content does not match file_hash", path);
+            return Err(MerkleError::FileMismatch(path));
+        }
+        println!("PASS: {} content matches file_hash", path);
+    }
+
+    let computed = walk(&inclusion.file_hash, &inclusion.proof)?;
+    if !computed.eq_ignore_ascii_case(&trusted_root) {
+        println!("FAIL: Proof walks to {}..., not the trusted root", &computed[..16]);
+        return Err(MerkleError::ProofFailed(trusted_root));
+    }
+
+    println!("PASS: Proof walks to the trusted root in {} step(s)", inclusion.proof.len());
+    println!();
+    println!("=== MERKLE VERIFICATION COMPLETE ===");
+    println!("Result: {} is included", inclusion.file);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// SHA3-512 of "a", "b" and "c", used as file hashes.
+    const A: &str = "697f2d856172cb8309d6b8b97dac4de344b549d4dee61edfb4962d8698b7fa803f4f93ff24393586e28b5b957ac3d1d369420ce53332712f997bd336d09ab02a";
+    const B: &str = "8446c46ee03793ba6e5813ba0db4480008926dd1d19efe2c8eb92f9034da974d2171ae483f29ce3a79ed4fdd621ae1ed14fe12532af95ddd0728779ce5aa842d";
+    const C: &str = "bfe4d7f7377116dc15f794d902621797b72b32396382de2b6e49d4f1d7eabdfddcfc3bc127bb67f92f9458a5733bb21804e7ccd56b4b6f81049339f477cd279d";
+
+    #[test]
+    fn root_known_answers() {
+        assert_eq!(
+            root(&[]).unwrap_or_default(),
+            "a69f73cca23a9ac5c8b567dc185a756e97c982164fe25859e0d1dcc1475c80a615b2123af1f5f94c11e3e9402c3ac558f500199d95b6d3e301758586281dcd26"
+        );
+        assert_eq!(
+            root(&[A]).unwrap_or_default(),
+            "3f7dcf575b9cbba079bc047fb5fc3ccdec49896cf4faddfe8d4241c9e77c389f07dc1b3974f805b769ccc14ec528178a9987e2fc8a988b0de36aa890825e7afd"
+        );
+        assert_eq!(
+            root(&[A, B]).unwrap_or_default(),
+            "67ab2e2f3fdb44ffeb43bd70c78e772ec5ebe5bca19620006dba3faf94ddbdba63aebb7e31581098048f3dbfaa633c6f14373bf1867b510658dd1d520c293eb1"
+        );
+        assert_eq!(
+            root(&[A, B, C]).unwrap_or_default(),
+            "0f432e13610b4e4bc4bde7ef4ad393029507e0e912f4dd10b219242b052cb905f4c374b1789ebe00c23ebe0f15e5455a4ea690e1cf13ddceea99232c78a9ba91"
+        );
+    }
+
+    #[test]
+    fn root_does_not_depend_on_input_order() {
+        assert_eq!(root(&[C, A, B]).unwrap_or_default(), root(&[A, B, C]).unwrap_or_default());
+    }
+
+    #[test]
+    fn proofs_walk_back_to_the_root() {
+        let hashes = [A, B, C];
+        let expected = root(&hashes).unwrap_or_default();
+        for hash in hashes {
+            let proof = prove(&hashes, hash).ok().flatten().unwrap_or_default();
+            assert_eq!(walk(hash, &proof).unwrap_or_default(), expected);
+        }
+    }
+
+    #[test]
+    fn proof_is_absent_for_a_non_member() {
+        assert!(matches!(prove(&[A, B], C), Ok(None)));
+    }
+
+    #[test]
+    fn root_rejects_non_hex_hashes() {
+        assert!(root(&[A, "zz"]).is_err());
+    }
+
+    #[test]
+    fn verify_rejects_non_hex_trusted_root() {
+        let result = verify("missing-proof.json".to_string(), "ééééééééééééééééé".to_string(), None);
+        assert!(matches!(result, Err(MerkleError::InvalidHash(_))));
+    }
+}
@@ -0,0 +1,308 @@
+//! Header schema migration module.
+//!
+//! Applies the migration steps of header contract §14 and §15 mechanically.
+//! Each step moves a header one schema version forward; steps are chained
+//! until the target version is reached. Structure comes from the target
+//! schema layout (see [`crate::frontmatter`]): the header is first normalized
+//! into that layout, filling missing fields with their defaults, and then
+//! each step rewrites the values that change between versions.
+//!
+//! Steps that need a human (an `authority_tier`, a version prefix, a
+//! signature) are reported as warnings rather than guessed.
+
+use serde_yaml::Value;
+use similar::TextDiff;
+use std::fs;
+use std::path::PathBuf;
+use thiserror::Error;
+
+use crate::common::{collect_documents, find_repo_root, get_yaml_field, relative_path};
+use crate::frontmatter::{self, Layout};
+use crate::seal;
+use crate::version;
+
+/// Errors during schema migration.
+#[derive(Debug, Error)]
+pub enum MigrateError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Cannot find specs/ directory")]
+    NoSpecsDir,
+
+    #[error("Unsupported target schema version: {0}")]
+    UnsupportedTarget(String),
+
+    #[error("Migration failed: {0} file(s) could not be migrated")]
+    MigrationFailed(u32),
+}
+
+/// New content and manual follow-up of a migration step, or why it failed.
+type StepResult = Result<(String, Vec<String>), String>;
+
+/// One schema migration step.
+struct Migration {
+    from: &'static str,
+    to: &'static str,
+    /// Rewrite the header values of a document already in the target layout.
+    /// Returns the new content and any manual follow-up required.
+    apply: fn(&str, &Value) -> StepResult,
+}
+
+/// Known migration steps, oldest first.
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        from: "1.0",
+        to: "2.0",
+        apply: migrate_1_0_to_2_0,
+    },
+    Migration {
+        from: "2.0",
+        to: "2.1",
+        apply: migrate_2_0_to_2_1,
+    },
+];
+
+/// Header layout of a schema version that documents can be migrated to.
+fn target_layout(version: &str) -> Option<&'static Layout> {
+    match version {
+        "2.1" => Some(&frontmatter::LAYOUT_V2_1),
+        _ => None,
+    }
+}
+
+/// Parse a document's header into a YAML value.
+fn header_value(content: &str) -> Result<Value, String> {
+    let (lines, _, _) = frontmatter::split_header(content).ok_or("missing YAML frontmatter")?;
+    serde_yaml::from_str(&lines.join("\n")).map_err(|e| format!("invalid YAML: {}", e))
+}
+
+/// The header's schema_version, accepting unquoted numbers.
+fn schema_version(header: &Value) -> Option<String> {
+    match header.get("schema_version")? {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// Set `schema_version` to `version`.
+fn set_schema_version(content: &str, version: &str) -> Result<String, String> {
+    seal::set_top_level_field(content, "schema_version", &seal::yaml_quote(version))
+        .ok_or_else(|| "schema_version not found".to_string())
+}
+
+/// Contract §14: v1.0 to v2.0.
+///
+/// The new sections and fields are added by the layout; what remains is the
+/// version bump and the version prefix, which cannot be chosen mechanically.
+fn migrate_1_0_to_2_0(content: &str, header: &Value) -> StepResult {
+    let mut notes = Vec::new();
+
+    if let Some(v) = get_yaml_field(header, "version") {
+        let has_prefix = version::parse(v).is_ok_and(|p| version::DOMAIN_PREFIXES.contains(&p.prefix.as_str()));
+        if !has_prefix {
+            notes.push(format!("version '{}' needs a prefix (XLIB, XGOV, ...)", v));
+        }
+    }
+
+    Ok((set_schema_version(content, "2.0")?, notes))
+}
+
+/// Contract §15: v2.0 to v2.1.
+///
+/// Maps legacy `active` to `approved`, records the content hash of approved
+/// and sealed documents, and adds the `authority` block if it is missing.
+///
+/// A signed `active` document is still mapped to `approved`: migration does
+/// not verify signatures, so it never seals. Such a document should be
+/// re-verified and sealed with `sign` (or checked with `verify-signature`)
+/// after migration.
+fn migrate_2_0_to_2_1(content: &str, header: &Value) -> StepResult {
+    let mut notes = Vec::new();
+    let mut updated = set_schema_version(content, "2.1")?;
+
+    let integrity = header.get("integrity");
+    let signed = integrity.and_then(|i| i.get("signature")).is_some_and(|s| !s.is_null());
+    let has_hash = integrity.and_then(|i| i.get("content_hash")).is_some_and(|s| !s.is_null());
+
+    let mut status = get_yaml_field(header, "status").unwrap_or_default().to_string();
+    if status == "active" {
+        status = "approved".to_string();
+        updated = seal::set_top_level_field(&updated, "status", &seal::yaml_quote(&status))
+            .ok_or("status not found")?;
+        if signed {
+            notes.push(
+                "status 'active' mapped to 'approved' although the document is signed; re-verify and seal it with sign or verify-signature"
+                    .to_string(),
+            );
+        }
+    }
+
+    if matches!(status.as_str(), "approved" | "sealed") && !has_hash {
+        let hash = seal::compute_content_hash(&updated).ok_or("missing YAML frontmatter")?;
+        updated = seal::set_section_field(&updated, "integrity", "hash_alg", &seal::yaml_quote(seal::CONTENT_HASH_ALG))
+            .and_then(|c| seal::set_section_field(&c, "integrity", "content_hash", &seal::yaml_quote(&hash)))
+            .ok_or("integrity section not found")?;
+    }
+    if status == "sealed" && !signed {
+        notes.push("status is 'sealed' but the document is not signed; run sign".to_string());
+    }
+
+    if header.get("authority").is_none() {
+        let block = ["authority:", "  repo: null", "  ref: null", "  version: null"].map(str::to_string);
+        updated = seal::insert_section(&updated, &block, "# --- Relationships ---")
+            .ok_or("missing YAML frontmatter")?;
+        notes.push("authority.repo and authority.ref must be set".to_string());
+    }
+
+    Ok((updated, notes))
+}
+
+/// Result of migrating one document.
+struct Migrated {
+    content: String,
+    from: String,
+    notes: Vec<String>,
+}
+
+/// Migrate one document to the `target` schema version.
+fn migrate_content(content: &str, target: &str, layout: &Layout) -> Result<Migrated, String> {
+    let from = schema_version(&header_value(content)?).ok_or("missing schema_version")?;
+    let mut version = from.clone();
+    let mut updated = content.to_string();
+    let mut notes = Vec::new();
+
+    while version != target {
+        let step = MIGRATIONS
+            .iter()
+            .find(|m| m.from == version)
+            .ok_or_else(|| format!("no migration from schema_version {} to {}", version, target))?;
+
+        let normalized = frontmatter::normalize_content(&updated, layout)?.content;
+        let (next, step_notes) = (step.apply)(&normalized, &header_value(&normalized)?)?;
+        updated = next;
+        notes.extend(step_notes);
+        version = step.to.to_string();
+    }
+
+    if from != target {
+        let normalized = frontmatter::normalize_content(&updated, layout)?;
+        notes.extend(normalized.missing.iter().map(|k| format!("required field {} is missing", k)));
+        updated = normalized.content;
+    }
+
+    Ok(Migrated {
+        content: updated,
+        from,
+        notes,
+    })
+}
+
+/// Run the `header migrate` command.
+///
+/// With `dry_run`, nothing is written and a unified diff is printed for
+/// each document that would change.
+pub fn migrate(
+    repo_root_arg: Option<String>,
+    file_path: Option<String>,
+    target: String,
+    dry_run: bool,
+) -> Result<(), MigrateError> {
+    let repo_root = match repo_root_arg {
+        Some(path) => PathBuf::from(path),
+        None => find_repo_root()?.ok_or(MigrateError::NoSpecsDir)?,
+    };
+    let layout = target_layout(&target).ok_or_else(|| MigrateError::UnsupportedTarget(target.clone()))?;
+
+    println!("=== XONAIX HEADER MIGRATION ===");
+    println!("Repository: {}", repo_root.display());
+    println!("Target Schema Version: {}", target);
+    println!("Mode: {}", if dry_run { "dry-run" } else { "write" });
+    println!();
+
+    let mut migrated = 0u32;
+    let mut current = 0u32;
+    let mut failed = 0u32;
+
+    for path in collect_documents(&repo_root, file_path)? {
+        let rel = relative_path(&path, &repo_root);
+        let content = fs::read_to_string(&path)?;
+
+        let result = match migrate_content(&content, &target, layout) {
+            Ok(r) => r,
+            Err(reason) => {
+                println!("FAIL: {}: {}", rel, reason);
+                failed += 1;
+                continue;
+            }
+        };
+
+        if result.content == content {
+            current += 1;
+            continue;
+        }
+
+        migrated += 1;
+        println!("MIGRATED: {} ({} -> {})", rel, result.from, target);
+        for note in &result.notes {
+            println!("WARN: {}: {}", rel, note);
+        }
+
+        if dry_run {
+            let old_name = format!("a/{}", rel);
+            let new_name = format!("b/{}", rel);
+            print!(
+                "{}",
+                TextDiff::from_lines(&content, &result.content)
+                    .unified_diff()
+                    .header(&old_name, &new_name)
+            );
+        } else {
+            fs::write(&path, &result.content)?;
+        }
+    }
+
+    println!();
+    println!("=== MIGRATION COMPLETE ===");
+    println!("{}: {}", if dry_run { "Would migrate" } else { "Migrated" }, migrated);
+    println!("Already current: {}", current);
+    println!("Failed: {}", failed);
+
+    if failed > 0 {
+        Err(MigrateError::MigrationFailed(failed))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v2_0(signature: &str) -> String {
+        format!(
+            "---\nschema_version: \"2.0\"\nstatus: \"active\"\nintegrity:\n  content_hash: \"ab\"\n  signature: {}\nauthority:\n  repo: null\n---\nbody\n",
+            signature
+        )
+    }
+
+    fn migrate_step(content: &str) -> StepResult {
+        migrate_2_0_to_2_1(content, &header_value(content)?)
+    }
+
+    /// Regression: a signed `active` document used to become `sealed` without verification.
+    #[test]
+    fn signed_active_becomes_approved() {
+        let result = migrate_step(&v2_0("\"00ff\""));
+        assert!(matches!(&result, Ok((content, notes))
+            if content.contains("status: \"approved\"") && notes.iter().any(|n| n.contains("re-verify"))));
+    }
+
+    #[test]
+    fn unsigned_active_becomes_approved() {
+        let result = migrate_step(&v2_0("null"));
+        assert!(matches!(&result, Ok((content, notes))
+            if content.contains("schema_version: \"2.1\"") && content.contains("status: \"approved\"") && notes.is_empty()));
+    }
+}
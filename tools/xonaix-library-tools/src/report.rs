@@ -11,6 +11,14 @@ use std::path::{Path, PathBuf};
 use thiserror::Error;
 use walkdir::WalkDir;
 
+use crate::anchor::{self, ZeropointAnchor};
+use crate::approval::{self, Approval, ApprovalRequirements};
+use crate::canonical;
+use crate::common::{extract_frontmatter, find_repo_root, should_exclude_from_report};
+use crate::seal;
+use crate::keyring::{self, Keyring};
+use crate::sign;
+
 /// Errors during report generation.
 #[derive(Debug, Error)]
 pub enum ReportError {
@@ -30,10 +38,7 @@ pub enum ReportError {
 /// Document header for parsing.
 #[derive(Debug, Deserialize)]
 struct DocumentHeader {
-    schema: Option<String>,
     schema_version: Option<String>,
-    repo: Option<String>,
-    path: Option<String>,
     unit_id: Option<String>,
     title: Option<String>,
     document_type: Option<String>,
@@ -41,9 +46,11 @@ struct DocumentHeader {
     trust_class: Option<serde_yaml::Value>,
     classification: Option<String>,
     owner: Option<String>,
-    approved_by: Option<serde_yaml::Value>,
     authority_tier: Option<String>,
     integrity: Option<Integrity>,
+    zeropoint_anchor: Option<ZeropointAnchor>,
+    approvals: Option<Vec<Approval>>,
+    approval_requirements: Option<ApprovalRequirements>,
     created: Option<String>,
     last_updated: Option<String>,
 }
@@ -97,9 +104,12 @@ pub struct IntegrityMetrics {
     pub without_content_hash: u32,
     pub with_signature: u32,
     pub without_signature: u32,
+    pub with_anchor: u32,
+    pub without_anchor: u32,
     pub fully_sealed: u32,
     pub content_hash_coverage_percent: f64,
     pub signature_coverage_percent: f64,
+    pub anchor_coverage_percent: f64,
 }
 
 #[derive(Debug, Serialize)]
@@ -123,7 +133,7 @@ pub struct DocumentReport {
     pub owner: Option<String>,
     pub schema_version: Option<String>,
     pub integrity: DocumentIntegrity,
-    pub governance_debt: Vec<String>,
+    pub governance_debt: Vec<Debt>,
     pub created: Option<String>,
     pub last_updated: Option<String>,
 }
@@ -132,6 +142,8 @@ pub struct DocumentReport {
 pub struct DocumentIntegrity {
     pub has_content_hash: bool,
     pub has_signature: bool,
+    /// Anchor present and verified against its ledger block
+    pub is_anchored: bool,
     pub hash_algorithm: Option<String>,
     pub signed_by: Option<String>,
     pub signed_at: Option<String>,
@@ -148,60 +160,76 @@ pub struct GovernanceDebt {
 #[derive(Debug, Serialize)]
 pub struct DebtItem {
     pub path: String,
-    pub debt_type: String,
+    pub debt_type: DebtKind,
     pub description: String,
-    pub severity: String,
+    pub severity: Severity,
 }
 
-/// Find repository root.
-fn find_repo_root() -> Result<PathBuf, ReportError> {
-    let mut current = std::env::current_dir()?;
-
-    if current.join("specs").exists() {
-        return Ok(current);
-    }
+/// Kind of governance debt, as reported in `debt_type`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DebtKind {
+    MissingContentHash,
+    MissingSignature,
+    InvalidSignature,
+    RevokedSignerKey,
+    InvalidAnchor,
+    InvalidApproval,
+    UnmetApprovalRequirements,
+    SchemaMigration,
+}
 
-    while let Some(parent) = current.parent() {
-        if parent.join("specs").exists() {
-            return Ok(parent.to_path_buf());
+impl DebtKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            DebtKind::MissingContentHash => "missing_content_hash",
+            DebtKind::MissingSignature => "missing_signature",
+            DebtKind::InvalidSignature => "invalid_signature",
+            DebtKind::RevokedSignerKey => "revoked_signer_key",
+            DebtKind::InvalidAnchor => "invalid_anchor",
+            DebtKind::InvalidApproval => "invalid_approval",
+            DebtKind::UnmetApprovalRequirements => "unmet_approval_requirements",
+            DebtKind::SchemaMigration => "schema_migration",
         }
-        current = parent.to_path_buf();
     }
-
-    Err(ReportError::NoSpecsDir)
 }
 
-/// Check if path should be excluded.
-fn should_exclude(path: &Path) -> bool {
-    let path_str = path.to_string_lossy();
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
 
-    path_str.contains("_deprecated")
-        || path_str.contains(".git")
-        || path_str.contains("target")
-        || path_str.contains("manifests")
+/// One governance debt finding on a document.
+///
+/// Serialized as its description, as in the per-document debt list.
+#[derive(Debug)]
+pub struct Debt {
+    pub kind: DebtKind,
+    pub severity: Severity,
+    pub description: String,
 }
 
-/// Extract YAML frontmatter from markdown content.
-fn extract_frontmatter(content: &str) -> Option<&str> {
-    if !content.starts_with("---") {
-        return None;
+impl Debt {
+    fn new(kind: DebtKind, severity: Severity, description: impl Into<String>) -> Self {
+        Debt {
+            kind,
+            severity,
+            description: description.into(),
+        }
     }
+}
 
-    let rest = &content[3..];
-    if let Some(end_idx) = rest.find("\n---") {
-        Some(&rest[..end_idx])
-    } else {
-        None
+impl Serialize for Debt {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.description)
     }
 }
 
 /// Check if a serde_yaml::Value is null.
 fn is_yaml_null(v: &Option<serde_yaml::Value>) -> bool {
-    match v {
-        None => true,
-        Some(serde_yaml::Value::Null) => true,
-        _ => false,
-    }
+    matches!(v, None | Some(serde_yaml::Value::Null))
 }
 
 /// Get string from yaml value.
@@ -213,7 +241,7 @@ fn yaml_to_string(v: &Option<serde_yaml::Value>) -> Option<String> {
 }
 
 /// Parse a single document and generate its report.
-fn parse_document(path: &Path, content: &str) -> Option<DocumentReport> {
+fn parse_document(repo_root: &Path, path: &Path, content: &str, keyring: Option<&Keyring>) -> Option<DocumentReport> {
     let frontmatter = extract_frontmatter(content)?;
     let header: DocumentHeader = serde_yaml::from_str(frontmatter).ok()?;
 
@@ -244,6 +272,22 @@ fn parse_document(path: &Path, content: &str) -> Option<DocumentReport> {
         .as_ref()
         .and_then(|i| yaml_to_string(&i.signed_at));
 
+    // An anchor counts only if it verifies against the computed content hash
+    // and the ledger
+    let mut anchor_problems = Vec::new();
+    let is_anchored = match &header.zeropoint_anchor {
+        Some(anchor) if anchor.is_populated() => {
+            let content_hash = seal::compute_content_hash(content);
+            match content_hash.map(|h| anchor::verify_document_anchor(repo_root, anchor, &h)) {
+                Some(Ok(problems)) => anchor_problems = problems,
+                Some(Err(e)) => anchor_problems.push(e.to_string()),
+                None => anchor_problems.push("no content hash to verify against".to_string()),
+            }
+            anchor_problems.is_empty()
+        }
+        _ => false,
+    };
+
     let trust_class = match &header.trust_class {
         Some(serde_yaml::Value::String(s)) => Some(s.clone()),
         _ => None,
@@ -251,24 +295,94 @@ fn parse_document(path: &Path, content: &str) -> Option<DocumentReport> {
 
     // Calculate governance debt
     let mut debt = Vec::new();
-    if let Some(status) = &header.status {
-        if status == "approved" && !has_content_hash {
-            debt.push("content_hash missing (approved status)".to_string());
-        }
-        if (status == "sealed" || status == "deprecated" || status == "superseded")
-            && !has_content_hash
-        {
-            debt.push("content_hash missing (sealed/deprecated/superseded)".to_string());
+    let sealed = matches!(header.status.as_deref(), Some("sealed" | "deprecated" | "superseded"));
+    if header.status.as_deref() == Some("approved") && !has_content_hash {
+        debt.push(Debt::new(
+            DebtKind::MissingContentHash,
+            Severity::Warning,
+            "content_hash missing (approved status)",
+        ));
+    }
+    if sealed && !has_content_hash {
+        debt.push(Debt::new(
+            DebtKind::MissingContentHash,
+            Severity::Error,
+            "content_hash missing (sealed/deprecated/superseded)",
+        ));
+    }
+    if sealed && !has_signature {
+        debt.push(Debt::new(
+            DebtKind::MissingSignature,
+            Severity::Error,
+            "signature missing (sealed/deprecated/superseded)",
+        ));
+    }
+
+    for problem in anchor_problems {
+        debt.push(Debt::new(
+            DebtKind::InvalidAnchor,
+            Severity::Error,
+            format!("zeropoint_anchor invalid: {}", problem),
+        ));
+    }
+
+    if (header.approvals.is_some() || header.approval_requirements.is_some())
+        && let Some(content_hash) = header
+            .integrity
+            .as_ref()
+            .and_then(|i| yaml_to_string(&i.content_hash))
+            .or_else(|| seal::compute_content_hash(content))
+    {
+        let approvals = header.approvals.as_deref().unwrap_or_default();
+        let eval = approval::evaluate(approvals, header.approval_requirements.as_ref(), &content_hash, keyring);
+        for error in eval.errors {
+            debt.push(Debt::new(
+                DebtKind::InvalidApproval,
+                Severity::Error,
+                format!("approval invalid: {}", error),
+            ));
         }
-        if (status == "sealed" || status == "deprecated" || status == "superseded")
-            && !has_signature
-        {
-            debt.push("signature missing (sealed/deprecated/superseded)".to_string());
+        let severity = if sealed { Severity::Error } else { Severity::Warning };
+        for unmet in eval.unmet {
+            debt.push(Debt::new(
+                DebtKind::UnmetApprovalRequirements,
+                severity,
+                format!("approval requirements not met: {}", unmet),
+            ));
         }
     }
 
     if header.schema_version.as_deref() == Some("2.0") {
-        debt.push("schema_version 2.0 (should migrate to 2.1)".to_string());
+        debt.push(Debt::new(
+            DebtKind::SchemaMigration,
+            Severity::Warning,
+            "schema_version 2.0 (should migrate to 2.1)",
+        ));
+    }
+
+    // Signatures remain valid if the key was revoked later, but must be redone
+    if let Some(kr) = keyring {
+        match sign::verify_document_signature(path, content, kr) {
+            Ok(Some(verified)) => {
+                if let Some(revoked_at) = verified.revoked_at {
+                    debt.push(Debt::new(
+                        DebtKind::RevokedSignerKey,
+                        Severity::Warning,
+                        format!(
+                            "signing key '{}' revoked at {} after signing (re-sign required)",
+                            verified.key_id,
+                            revoked_at.to_rfc3339()
+                        ),
+                    ));
+                }
+            }
+            Ok(None) => {}
+            Err(reason) => debt.push(Debt::new(
+                DebtKind::InvalidSignature,
+                Severity::Error,
+                format!("signature invalid: {}", reason),
+            )),
+        }
     }
 
     Some(DocumentReport {
@@ -285,6 +399,7 @@ fn parse_document(path: &Path, content: &str) -> Option<DocumentReport> {
         integrity: DocumentIntegrity {
             has_content_hash,
             has_signature,
+            is_anchored,
             hash_algorithm: hash_alg,
             signed_by,
             signed_at,
@@ -299,10 +414,11 @@ fn parse_document(path: &Path, content: &str) -> Option<DocumentReport> {
 pub fn generate_report(repo_root_arg: Option<String>) -> Result<GovernanceReport, ReportError> {
     let repo_root = match repo_root_arg {
         Some(path) => PathBuf::from(path),
-        None => find_repo_root()?,
+        None => find_repo_root()?.ok_or(ReportError::NoSpecsDir)?,
     };
 
     let specs_dir = repo_root.join("specs");
+    let keyring = keyring::load_keyring(&repo_root, None).ok();
     let mut documents = Vec::new();
 
     // Scan all markdown files
@@ -311,7 +427,7 @@ pub fn generate_report(repo_root_arg: Option<String>) -> Result<GovernanceReport
         .filter_map(|e| e.ok())
     {
         let path = entry.path();
-        if !path.is_file() || should_exclude(path) {
+        if !path.is_file() || should_exclude_from_report(path) {
             continue;
         }
 
@@ -324,7 +440,7 @@ pub fn generate_report(repo_root_arg: Option<String>) -> Result<GovernanceReport
             Err(_) => continue,
         };
 
-        if let Some(doc) = parse_document(path, &content) {
+        if let Some(doc) = parse_document(&repo_root, path, &content, keyring.as_ref()) {
             documents.push(doc);
         }
     }
@@ -338,6 +454,7 @@ pub fn generate_report(repo_root_arg: Option<String>) -> Result<GovernanceReport
 
     let mut with_content_hash = 0u32;
     let mut with_signature = 0u32;
+    let mut with_anchor = 0u32;
     let mut v2_1_docs = 0u32;
     let mut v2_0_docs = 0u32;
     let mut other_version = 0u32;
@@ -382,6 +499,9 @@ pub fn generate_report(repo_root_arg: Option<String>) -> Result<GovernanceReport
         if doc.integrity.has_signature {
             with_signature += 1;
         }
+        if doc.integrity.is_anchored {
+            with_anchor += 1;
+        }
 
         // Schema version metrics
         match doc.schema_version.as_deref() {
@@ -394,30 +514,14 @@ pub fn generate_report(repo_root_arg: Option<String>) -> Result<GovernanceReport
         // Governance debt
         if !doc.governance_debt.is_empty() {
             docs_with_debt += 1;
-            for debt_desc in &doc.governance_debt {
-                let debt_type = if debt_desc.contains("content_hash") {
-                    "missing_content_hash"
-                } else if debt_desc.contains("signature") {
-                    "missing_signature"
-                } else if debt_desc.contains("schema_version") {
-                    "schema_migration"
-                } else {
-                    "other"
-                };
-
-                *debt_by_type.entry(debt_type.to_string()).or_insert(0) += 1;
-
-                let severity = if debt_desc.contains("sealed") || debt_desc.contains("deprecated") {
-                    "error"
-                } else {
-                    "warning"
-                };
+            for item in &doc.governance_debt {
+                *debt_by_type.entry(item.kind.as_str().to_string()).or_insert(0) += 1;
 
                 debt_items.push(DebtItem {
                     path: doc.path.clone(),
-                    debt_type: debt_type.to_string(),
-                    description: debt_desc.clone(),
-                    severity: severity.to_string(),
+                    debt_type: item.kind,
+                    description: item.description.clone(),
+                    severity: item.severity,
                 });
             }
         }
@@ -451,9 +555,12 @@ pub fn generate_report(repo_root_arg: Option<String>) -> Result<GovernanceReport
                 without_content_hash: total.saturating_sub(with_content_hash),
                 with_signature,
                 without_signature: total.saturating_sub(with_signature),
+                with_anchor,
+                without_anchor: total.saturating_sub(with_anchor),
                 fully_sealed,
                 content_hash_coverage_percent: (with_content_hash as f64 / total_f64) * 100.0,
                 signature_coverage_percent: (with_signature as f64 / total_f64) * 100.0,
+                anchor_coverage_percent: (with_anchor as f64 / total_f64) * 100.0,
             },
             schema_metrics: SchemaMetrics {
                 v2_1_documents: v2_1_docs,
@@ -537,6 +644,12 @@ fn print_table(report: &GovernanceReport) {
         report.summary.integrity_metrics.with_signature,
         report.summary.total_documents
     );
+    println!(
+        "  Anchor Coverage:       {:.1}% ({}/{})",
+        report.summary.integrity_metrics.anchor_coverage_percent,
+        report.summary.integrity_metrics.with_anchor,
+        report.summary.total_documents
+    );
     println!(
         "  Fully Sealed:          {}",
         report.summary.integrity_metrics.fully_sealed
@@ -574,7 +687,7 @@ fn print_table(report: &GovernanceReport) {
     if !report.governance_debt.debt_items.is_empty() {
         println!("Debt Items:");
         for item in &report.governance_debt.debt_items {
-            let severity_marker = if item.severity == "error" { "!" } else { "?" };
+            let severity_marker = if item.severity == Severity::Error { "!" } else { "?" };
             println!("  [{}] {}", severity_marker, item.path);
             println!("      {}", item.description);
         }
@@ -593,6 +706,10 @@ fn print_summary(report: &GovernanceReport) {
         "Signature Coverage: {:.1}%",
         report.summary.integrity_metrics.signature_coverage_percent
     );
+    println!(
+        "Anchor Coverage: {:.1}%",
+        report.summary.integrity_metrics.anchor_coverage_percent
+    );
     println!(
         "Governance Debt: {} items in {} documents",
         report.governance_debt.total_debt_items, report.governance_debt.documents_with_debt
@@ -617,7 +734,7 @@ pub fn run(
     let report = generate_report(repo_root)?;
 
     let output = match format {
-        OutputFormat::Json => serde_json::to_string(&report)?,
+        OutputFormat::Json => canonical::to_canonical_string(&report)?,
         OutputFormat::JsonPretty => serde_json::to_string_pretty(&report)?,
         OutputFormat::Table => {
             print_table(&report);
@@ -638,3 +755,105 @@ pub fn run(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keyring::tests::test_keyring;
+    use crate::sign::tests::signed_document;
+
+    fn debt_of(content: &str, keyring: Option<&Keyring>) -> Vec<(DebtKind, Severity)> {
+        parse_document(Path::new("/nonexistent-repo"), Path::new("DOC.md"), content, keyring)
+            .map(|d| d.governance_debt.iter().map(|i| (i.kind, i.severity)).collect())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn valid_signature_has_no_debt() {
+        let content = signed_document("2025-02-01T00:00:00Z", "2025-02-01T00:00:00Z");
+        assert_eq!(debt_of(&content, Some(&test_keyring(None))), Vec::new());
+    }
+
+    #[test]
+    fn key_revoked_after_signing_is_revoked_signer_key_debt() {
+        let content = signed_document("2025-02-01T00:00:00Z", "2025-02-01T00:00:00Z");
+        assert_eq!(
+            debt_of(&content, Some(&test_keyring(Some("2025-03-01T00:00:00Z")))),
+            vec![(DebtKind::RevokedSignerKey, Severity::Warning)]
+        );
+    }
+
+    /// Regression: an error mentioning a revoked key is an invalid signature, not revocation debt.
+    #[test]
+    fn signature_after_revocation_is_an_invalid_signature() {
+        let content = signed_document("2025-04-01T00:00:00Z", "2025-04-01T00:00:00Z");
+        assert_eq!(
+            debt_of(&content, Some(&test_keyring(Some("2025-03-01T00:00:00Z")))),
+            vec![(DebtKind::InvalidSignature, Severity::Error)]
+        );
+    }
+
+    #[test]
+    fn missing_integrity_is_classified_by_status() {
+        let approved = "---\nstatus: \"approved\"\nintegrity:\n  content_hash: null\n---\nbody\n";
+        assert_eq!(debt_of(approved, None), vec![(DebtKind::MissingContentHash, Severity::Warning)]);
+
+        let sealed = approved.replace("approved", "sealed");
+        assert_eq!(
+            debt_of(&sealed, None),
+            vec![
+                (DebtKind::MissingContentHash, Severity::Error),
+                (DebtKind::MissingSignature, Severity::Error)
+            ]
+        );
+    }
+
+    /// A document anchored under `anchored_hash` in a fresh ledger at `repo_root`.
+    fn anchored_document(repo_root: &Path, anchored_hash: &str, body: &str) -> String {
+        use crate::anchor::{BlockEntry, FileLedger};
+
+        let entry = BlockEntry {
+            content_hash: anchored_hash.to_string(),
+            path: "DOC.md".to_string(),
+        };
+        let block = FileLedger::open(repo_root, anchor::DEFAULT_LEDGER_ID, None).and_then(|mut ledger| {
+            anchor::tests::push_block(&mut ledger, vec![entry], "2026-01-01T00:00:00Z".to_string())
+        });
+        let (height, block_hash, root) = block
+            .map(|b| (b.block_height, b.hash().unwrap_or_default(), b.merkle_root))
+            .unwrap_or_default();
+        format!(
+            "---\nstatus: \"sealed\"\nintegrity:\n  content_hash: \"{hash}\"\nzeropoint_anchor:\n  ledger_id: \"{ledger}\"\n  block_height: {height}\n  block_hash: \"{block_hash}\"\n  merkle_root: \"{root}\"\n  merkle_proof: []\n  anchored_at: \"2026-01-01T00:00:00Z\"\n  anchor_ref: \"{reference}\"\n---\n{body}",
+            hash = anchored_hash,
+            ledger = anchor::DEFAULT_LEDGER_ID,
+            reference = anchor::anchor_ref(anchor::DEFAULT_LEDGER_ID, height, anchored_hash),
+        )
+    }
+
+    /// Regression: the anchor is checked against the body, not the stored content_hash.
+    #[test]
+    fn anchor_uses_the_computed_content_hash() {
+        let repo_root = std::env::temp_dir().join(format!("xlib-report-anchor-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&repo_root);
+
+        let body = "Anchored body.\n";
+        let computed = seal::compute_content_hash(&format!("---\n---\n{}", body)).unwrap_or_default();
+        let anchored = |hash: &str| {
+            let content = anchored_document(&repo_root, hash, body);
+            let report = parse_document(&repo_root, Path::new("DOC.md"), &content, None);
+            let _ = fs::remove_dir_all(&repo_root);
+            report.is_some_and(|r| r.integrity.is_anchored)
+        };
+
+        assert!(anchored(&computed));
+        assert!(!anchored(&"ab".repeat(64)));
+    }
+
+    #[test]
+    fn debt_serializes_as_its_description() {
+        let debt = Debt::new(DebtKind::SchemaMigration, Severity::Warning, "schema_version 2.0");
+        assert_eq!(serde_json::to_string(&debt).unwrap_or_default(), "\"schema_version 2.0\"");
+        assert_eq!(serde_json::to_string(&DebtKind::RevokedSignerKey).unwrap_or_default(), "\"revoked_signer_key\"");
+        assert_eq!(DebtKind::RevokedSignerKey.as_str(), "revoked_signer_key");
+    }
+}
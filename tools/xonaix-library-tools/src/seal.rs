@@ -0,0 +1,336 @@
+//! Content seal module.
+//!
+//! Computes SHA3-512 content hashes over document bodies as defined in
+//! ZEROPOINT_INTEGRATION_SPEC §3.3 and records them in document headers.
+
+use serde::Deserialize;
+use sha3::{Digest, Sha3_512};
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use walkdir::WalkDir;
+
+use crate::common::{body_offset, extract_frontmatter, find_repo_root, should_exclude};
+
+/// Errors during content sealing.
+#[derive(Debug, Error)]
+pub enum SealError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Cannot find specs/ directory")]
+    NoSpecsDir,
+
+    #[error("{0}: missing or invalid YAML frontmatter")]
+    InvalidHeader(PathBuf),
+
+    #[error("{0}: status is '{1}', only 'approved' documents can be hashed")]
+    NotApproved(PathBuf, String),
+
+    #[error("{0}: header has no integrity section")]
+    MissingIntegrity(PathBuf),
+
+    #[error("Content hash failed: {0} file(s) could not be hashed")]
+    HashFailed(u32),
+}
+
+/// Hash algorithm recorded in `integrity.hash_alg`.
+pub(crate) const CONTENT_HASH_ALG: &str = "SHA3-512";
+
+/// Header fields needed for sealing.
+#[derive(Debug, Deserialize)]
+struct SealHeader {
+    status: Option<String>,
+}
+
+/// Compute the SHA3-512 content hash of a document.
+///
+/// The hash covers the body after the closing `---` of the header,
+/// normalized to LF line endings and trimmed of surrounding whitespace.
+/// Returns `None` when the document has no frontmatter.
+pub(crate) fn compute_content_hash(content: &str) -> Option<String> {
+    let body = &content[body_offset(content)?..];
+    let normalized = body.replace("\r\n", "\n").replace('\r', "\n");
+
+    let mut hasher = Sha3_512::new();
+    hasher.update(normalized.trim().as_bytes());
+    Some(format!("{:x}", hasher.finalize()))
+}
+
+/// Quote a string as a YAML double-quoted scalar.
+pub(crate) fn yaml_quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Set `section.key` in the frontmatter to a raw YAML scalar, in place.
+///
+/// Only the matching line is rewritten so comments, ordering and the body
+/// are preserved. A missing key is appended to the end of the section.
+/// Returns `None` if the frontmatter or the section does not exist.
+pub(crate) fn set_section_field(content: &str, section: &str, key: &str, value: &str) -> Option<String> {
+    let header_end = body_offset(content)?;
+    let section_line = format!("{}:", section);
+    let key_prefix = format!("{}:", key);
+
+    let mut lines: Vec<String> = content[..header_end].split('\n').map(str::to_string).collect();
+
+    let start = lines.iter().position(|l| l.trim_end() == section_line)?;
+
+    // Blank and comment lines do not end the section; it ends after its last
+    // indented line
+    let mut end = start + 1;
+    for (i, line) in lines.iter().enumerate().skip(start + 1) {
+        let line = line.trim_end();
+        if line.starts_with(' ') {
+            end = i + 1;
+        } else if !line.is_empty() && !line.starts_with('#') {
+            break;
+        }
+    }
+
+    let indent = lines[start + 1..end]
+        .iter()
+        .find(|l| !l.trim().is_empty() && !l.trim_start().starts_with('#'))
+        .map(|l| l[..l.len() - l.trim_start().len()].to_string())
+        .unwrap_or_else(|| "  ".to_string());
+    let new_line = format!("{}{} {}", indent, key_prefix, value);
+
+    match lines[start + 1..end]
+        .iter()
+        .position(|l| l.trim_start().starts_with(&key_prefix))
+    {
+        Some(idx) => {
+            let line = &mut lines[start + 1 + idx];
+            let eol = if line.ends_with('\r') { "\r" } else { "" };
+            *line = format!("{}{}", new_line, eol);
+        }
+        None => {
+            let eol = if lines[start].ends_with('\r') { "\r" } else { "" };
+            lines.insert(end, format!("{}{}", new_line, eol));
+        }
+    }
+
+    Some(format!("{}{}", lines.join("\n"), &content[header_end..]))
+}
+
+/// Set a top-level frontmatter key to a raw YAML scalar, in place.
+///
+/// Returns `None` if the frontmatter or the key does not exist.
+pub(crate) fn set_top_level_field(content: &str, key: &str, value: &str) -> Option<String> {
+    let header_end = body_offset(content)?;
+    let key_prefix = format!("{}:", key);
+
+    let mut lines: Vec<String> = content[..header_end].split('\n').map(str::to_string).collect();
+    let line = lines.iter_mut().find(|l| l.starts_with(&key_prefix))?;
+    let eol = if line.ends_with('\r') { "\r" } else { "" };
+    *line = format!("{} {}{}", key_prefix, value, eol);
+
+    Some(format!("{}{}", lines.join("\n"), &content[header_end..]))
+}
+
+/// Insert a new header section before the line `before`.
+///
+/// `lines` are given without line endings and take the document's line
+/// ending. When `before` is not present the section is appended at the end
+/// of the frontmatter. Returns `None` if the frontmatter does not exist.
+pub(crate) fn insert_section(content: &str, lines: &[String], before: &str) -> Option<String> {
+    let header_end = body_offset(content)?;
+    let eol = if content[..header_end].contains("\r\n") { "\r" } else { "" };
+
+    let mut header_lines: Vec<String> = content[..header_end].split('\n').map(str::to_string).collect();
+    let mut block: Vec<String> = lines.iter().map(|l| format!("{}{}", l, eol)).collect();
+
+    match header_lines.iter().position(|l| l.trim_end() == before) {
+        Some(pos) => {
+            block.push(eol.to_string());
+            let _ = header_lines.splice(pos..pos, block);
+        }
+        None => {
+            // Before the closing `---`
+            let pos = header_lines.len() - 1;
+            block.insert(0, eol.to_string());
+            let _ = header_lines.splice(pos..pos, block);
+        }
+    }
+
+    Some(format!("{}{}", header_lines.join("\n"), &content[header_end..]))
+}
+
+/// Outcome of hashing a single document.
+enum HashOutcome {
+    Hashed(String),
+    Unchanged,
+}
+
+/// Compute and record the content hash for one approved document.
+fn hash_document(path: &Path) -> Result<HashOutcome, SealError> {
+    let content = fs::read_to_string(path)?;
+
+    let frontmatter = extract_frontmatter(&content)
+        .ok_or_else(|| SealError::InvalidHeader(path.to_path_buf()))?;
+    let header: SealHeader = serde_yaml::from_str(frontmatter)
+        .map_err(|_| SealError::InvalidHeader(path.to_path_buf()))?;
+
+    let status = header.status.unwrap_or_default();
+    if status != "approved" {
+        return Err(SealError::NotApproved(path.to_path_buf(), status));
+    }
+
+    let hash = compute_content_hash(&content)
+        .ok_or_else(|| SealError::InvalidHeader(path.to_path_buf()))?;
+
+    let updated = set_section_field(&content, "integrity", "hash_alg", &yaml_quote(CONTENT_HASH_ALG))
+        .and_then(|c| set_section_field(&c, "integrity", "content_hash", &yaml_quote(&hash)))
+        .ok_or_else(|| SealError::MissingIntegrity(path.to_path_buf()))?;
+
+    if updated == content {
+        return Ok(HashOutcome::Unchanged);
+    }
+
+    fs::write(path, updated)?;
+    Ok(HashOutcome::Hashed(hash))
+}
+
+/// Run the `seal hash` command.
+pub fn hash(repo_root_arg: Option<String>, file_path: Option<String>) -> Result<(), SealError> {
+    let repo_root = match repo_root_arg {
+        Some(path) => PathBuf::from(path),
+        None => find_repo_root()?.ok_or(SealError::NoSpecsDir)?,
+    };
+
+    println!("=== XONAIX LIBRARY CONTENT HASH ===");
+    println!("Repository: {}", repo_root.display());
+    println!("Hash Algorithm: {}", CONTENT_HASH_ALG);
+    println!();
+
+    let mut hashed = 0u32;
+    let mut unchanged = 0u32;
+    let mut skipped = 0u32;
+    let mut failed = 0u32;
+
+    let report = |path: &Path, outcome: &HashOutcome| match outcome {
+        HashOutcome::Hashed(h) => println!("HASHED: {} ({}...)", path.display(), &h[..16]),
+        HashOutcome::Unchanged => println!("UNCHANGED: {}", path.display()),
+    };
+
+    if let Some(file) = file_path {
+        let path = PathBuf::from(&file);
+        if !path.exists() {
+            return Err(SealError::Io(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("File not found: {}", file),
+            )));
+        }
+
+        let outcome = hash_document(&path)?;
+        report(&path, &outcome);
+        match outcome {
+            HashOutcome::Hashed(_) => hashed += 1,
+            HashOutcome::Unchanged => unchanged += 1,
+        }
+    } else {
+        for entry in WalkDir::new(repo_root.join("specs"))
+            .sort_by_file_name()
+            .into_iter()
+            .filter_map(|e| e.ok())
+        {
+            let path = entry.path();
+            if !path.is_file() || should_exclude(path) {
+                continue;
+            }
+            if path.extension().and_then(|e| e.to_str()) != Some("md") {
+                continue;
+            }
+
+            match hash_document(path) {
+                Ok(outcome) => {
+                    report(path, &outcome);
+                    match outcome {
+                        HashOutcome::Hashed(_) => hashed += 1,
+                        HashOutcome::Unchanged => unchanged += 1,
+                    }
+                }
+                Err(SealError::NotApproved(p, status)) => {
+                    println!("SKIP: {} (status '{}')", p.display(), status);
+                    skipped += 1;
+                }
+                Err(e) => {
+                    println!("FAIL: {}", e);
+                    failed += 1;
+                }
+            }
+        }
+    }
+
+    println!();
+    println!("=== CONTENT HASH COMPLETE ===");
+    println!("Hashed: {}", hashed);
+    println!("Unchanged: {}", unchanged);
+    println!("Skipped: {}", skipped);
+    println!("Failed: {}", failed);
+
+    if failed > 0 {
+        Err(SealError::HashFailed(failed))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// SHA3-512("abc") from FIPS 202.
+    const SHA3_512_ABC: &str = "b751850b1a57168a5693cd924b6b096e08f621827444f70d884f5d0240d2712e10e116e9192af3c91a7ec57647e3934057340b4cf408d5a56592f8274eec53f0";
+
+    #[test]
+    fn content_hash_known_answer() {
+        assert_eq!(compute_content_hash("---\ntitle: x\n---\nabc\n").as_deref(), Some(SHA3_512_ABC));
+    }
+
+    #[test]
+    fn content_hash_ignores_line_endings_and_surrounding_whitespace() {
+        assert_eq!(compute_content_hash("---\r\ntitle: x\r\n---\r\n\r\nabc\r\n\r\n").as_deref(), Some(SHA3_512_ABC));
+    }
+
+    #[test]
+    fn content_hash_requires_frontmatter() {
+        assert_eq!(compute_content_hash("abc"), None);
+        assert_eq!(compute_content_hash("---\ntitle: x\n"), None);
+    }
+
+    #[test]
+    fn horizontal_rule_in_body_is_not_the_header_end() {
+        let content = "---\ntitle: x\n---\nabc\n\n-----\n\nmore\n";
+        assert_eq!(extract_frontmatter(content), Some("\ntitle: x"));
+        assert_eq!(
+            compute_content_hash(content),
+            compute_content_hash("---\ntitle: y\n---\nabc\n\n-----\n\nmore")
+        );
+    }
+
+    #[test]
+    fn set_section_field_rewrites_only_the_key() {
+        let content = "---\nintegrity:\n  hash_alg: null\n  content_hash: null\nstatus: \"approved\"\n---\nbody\n";
+        let updated = set_section_field(content, "integrity", "content_hash", &yaml_quote("ab")).unwrap_or_default();
+        assert_eq!(
+            updated,
+            "---\nintegrity:\n  hash_alg: null\n  content_hash: \"ab\"\nstatus: \"approved\"\n---\nbody\n"
+        );
+    }
+
+    /// Regression: a blank or comment line ended the section, so the key was added a second time.
+    #[test]
+    fn set_section_field_scans_past_blank_and_comment_lines() {
+        let content = "---\nintegrity:\n  hash_alg: null\n\n# Filled in by seal hash\n  content_hash: null\n\n# --- Lifecycle ---\nstatus: \"approved\"\n---\nbody\n";
+
+        let updated = set_section_field(content, "integrity", "content_hash", &yaml_quote("ab")).unwrap_or_default();
+        assert_eq!(updated, content.replace("content_hash: null", "content_hash: \"ab\""));
+
+        let added = set_section_field(content, "integrity", "signature", &yaml_quote("sig")).unwrap_or_default();
+        assert_eq!(
+            added,
+            content.replace("  content_hash: null\n", "  content_hash: null\n  signature: \"sig\"\n")
+        );
+    }
+}
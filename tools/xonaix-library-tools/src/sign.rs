@@ -0,0 +1,501 @@
+//! Document signing module.
+//!
+//! Signs document content hashes with Ed25519 and verifies stored signatures
+//! against the trusted signer keyring.
+//!
+//! The signature covers the content hash together with `signed_by` and
+//! `signed_at`, so neither the signer nor the signing time can be changed
+//! without invalidating it.
+
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+use crate::canonical;
+use crate::common::{collect_documents, extract_frontmatter, find_repo_root, get_yaml_string};
+use crate::keyring::{self, Keyring, KeyringError};
+use crate::seal;
+
+/// Domain separator of document signatures.
+const SIGNATURE_DOMAIN: &str = "xonaix-document-signature/v1";
+
+/// Errors during signing or signature verification.
+#[derive(Debug, Error)]
+pub enum SignError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error(transparent)]
+    Keyring(#[from] KeyringError),
+
+    #[error("Cannot find specs/ directory")]
+    NoSpecsDir,
+
+    #[error("Invalid key file {0}: {1}")]
+    InvalidKeyFile(PathBuf, String),
+
+    #[error("{0}: missing or invalid YAML frontmatter")]
+    InvalidHeader(PathBuf),
+
+    #[error("{0}: status is '{1}', only 'approved' documents can be signed")]
+    NotApproved(PathBuf, String),
+
+    #[error("{0}: content_hash is missing, run `seal hash` first")]
+    MissingContentHash(PathBuf),
+
+    #[error("{0}: content tampered, stored content_hash does not match the document body")]
+    ContentTampered(PathBuf),
+
+    #[error("Signing failed: {0} document(s) could not be signed")]
+    SigningFailed(u32),
+
+    #[error("Signature verification failed: {0} document(s) failed")]
+    VerificationFailed(u32),
+}
+
+/// Statement signed for a document.
+#[derive(Debug, Serialize)]
+struct SignedPayload<'a> {
+    content_hash: &'a str,
+    domain: &'a str,
+    signed_at: &'a str,
+    signed_by: &'a str,
+}
+
+/// Header fields needed for signing.
+#[derive(Debug, Deserialize)]
+struct SignHeader {
+    status: Option<String>,
+    trust_class: Option<serde_yaml::Value>,
+    authority_tier: Option<String>,
+    integrity: Option<SignIntegrity>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SignIntegrity {
+    content_hash: Option<serde_yaml::Value>,
+    signature: Option<serde_yaml::Value>,
+    signed_by: Option<serde_yaml::Value>,
+    signed_at: Option<serde_yaml::Value>,
+}
+
+/// A document signature that verified against the keyring.
+#[derive(Debug)]
+pub(crate) struct VerifiedSignature {
+    pub signer: String,
+    pub key_id: String,
+    /// Set when the signing key was revoked after the document was signed.
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+/// Parse a document header.
+fn parse_header(path: &Path, content: &str) -> Result<SignHeader, SignError> {
+    let frontmatter = extract_frontmatter(content).ok_or_else(|| SignError::InvalidHeader(path.to_path_buf()))?;
+    serde_yaml::from_str(frontmatter).map_err(|_| SignError::InvalidHeader(path.to_path_buf()))
+}
+
+/// Load an Ed25519 signing key from a file containing the hex-encoded 32-byte seed.
+pub(crate) fn load_signing_key(path: &Path) -> Result<SigningKey, SignError> {
+    let content = fs::read_to_string(path)?;
+    let bytes = hex::decode(content.trim()).map_err(|e| SignError::InvalidKeyFile(path.to_path_buf(), e.to_string()))?;
+    let seed: [u8; 32] = bytes.try_into().map_err(|b: Vec<u8>| {
+        SignError::InvalidKeyFile(path.to_path_buf(), format!("expected 32 bytes, got {}", b.len()))
+    })?;
+    Ok(SigningKey::from_bytes(&seed))
+}
+
+/// Bytes signed for a document: the canonical JSON of the content hash,
+/// signer and signing time, under the document signature domain.
+pub(crate) fn signature_message(content_hash: &str, signed_by: &str, signed_at: &str) -> Result<Vec<u8>, serde_json::Error> {
+    let content_hash = content_hash.to_ascii_lowercase();
+    let payload = SignedPayload {
+        content_hash: &content_hash,
+        domain: SIGNATURE_DOMAIN,
+        signed_at,
+        signed_by,
+    };
+    Ok(canonical::to_canonical_string(&payload)?.into_bytes())
+}
+
+/// Sign one approved document and transition it to `sealed`.
+///
+/// The signer must be authorized for the document's authority tier and
+/// trust class; nothing is written otherwise.
+fn sign_document(path: &Path, key: &SigningKey, signer: &str, keyring: &Keyring) -> Result<String, SignError> {
+    let content = fs::read_to_string(path)?;
+    let header = parse_header(path, &content)?;
+
+    let status = header.status.unwrap_or_default();
+    if status != "approved" {
+        return Err(SignError::NotApproved(path.to_path_buf(), status));
+    }
+
+    let stored = header
+        .integrity
+        .as_ref()
+        .and_then(|i| get_yaml_string(&i.content_hash))
+        .ok_or_else(|| SignError::MissingContentHash(path.to_path_buf()))?
+        .to_string();
+
+    let computed = seal::compute_content_hash(&content).ok_or_else(|| SignError::InvalidHeader(path.to_path_buf()))?;
+    if !stored.eq_ignore_ascii_case(&computed) {
+        return Err(SignError::ContentTampered(path.to_path_buf()));
+    }
+
+    keyring.authorize(signer, header.authority_tier.as_deref(), get_yaml_string(&header.trust_class))?;
+
+    let signed_at = Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+    let signature = key.sign(&signature_message(&computed, signer, &signed_at)?);
+    let signature_hex = hex::encode(signature.to_bytes());
+
+    let updated = seal::set_section_field(&content, "integrity", "signature", &seal::yaml_quote(&signature_hex))
+        .and_then(|c| seal::set_section_field(&c, "integrity", "signed_by", &seal::yaml_quote(signer)))
+        .and_then(|c| seal::set_section_field(&c, "integrity", "signed_at", &seal::yaml_quote(&signed_at)))
+        .and_then(|c| seal::set_top_level_field(&c, "status", &seal::yaml_quote("sealed")))
+        .ok_or_else(|| SignError::InvalidHeader(path.to_path_buf()))?;
+
+    fs::write(path, updated)?;
+    Ok(signature_hex)
+}
+
+/// Run the `sign` command.
+///
+/// Refuses to sign unless the keyring lists `signer` with this key, valid
+/// and not revoked now, so every signature written will verify.
+pub fn sign(
+    repo_root_arg: Option<String>,
+    file_path: Option<String>,
+    key_path: String,
+    signer: String,
+    keyring_path: Option<String>,
+) -> Result<(), SignError> {
+    let repo_root = match repo_root_arg {
+        Some(path) => PathBuf::from(path),
+        None => find_repo_root()?.ok_or(SignError::NoSpecsDir)?,
+    };
+
+    let key = load_signing_key(Path::new(&key_path))?;
+    let keyring = keyring::load_keyring(&repo_root, keyring_path.as_deref().map(Path::new))?;
+    let key_id = keyring.signing_key(&signer, &key.verifying_key(), Utc::now())?.key_id.clone();
+
+    println!("=== XONAIX LIBRARY DOCUMENT SIGNING ===");
+    println!("Repository: {}", repo_root.display());
+    println!("Signer: {}", signer);
+    println!("Key ID: {}", key_id);
+    println!("Public Key: {}", hex::encode(key.verifying_key().to_bytes()));
+    println!();
+
+    let single = file_path.is_some();
+    let mut signed = 0u32;
+    let mut skipped = 0u32;
+    let mut failed = 0u32;
+
+    for path in collect_documents(&repo_root, file_path)? {
+        match sign_document(&path, &key, &signer, &keyring) {
+            Ok(sig) => {
+                println!("SIGNED: {} ({}...)", path.display(), &sig[..16]);
+                signed += 1;
+            }
+            Err(SignError::NotApproved(p, status)) if !single => {
+                println!("SKIP: {} (status '{}')", p.display(), status);
+                skipped += 1;
+            }
+            Err(SignError::MissingContentHash(p)) if !single => {
+                println!("SKIP: {} (no content_hash)", p.display());
+                skipped += 1;
+            }
+            Err(e) if !single => {
+                println!("FAIL: {}: {}", path.display(), e);
+                failed += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    println!();
+    println!("=== SIGNING COMPLETE ===");
+    println!("Signed: {}", signed);
+    println!("Skipped: {}", skipped);
+    println!("Failed: {}", failed);
+
+    if failed > 0 {
+        Err(SignError::SigningFailed(failed))
+    } else {
+        Ok(())
+    }
+}
+
+/// Verify the signature of a single document against the keyring.
+///
+/// The key is selected by `integrity.signed_at`, so documents signed before a
+/// key rotation or revocation keep verifying; a later revocation is reported
+/// through [`VerifiedSignature::revoked_at`].
+/// Returns `Ok(None)` for unsigned documents that do not require a signature.
+pub(crate) fn verify_document_signature(
+    path: &Path,
+    content: &str,
+    keyring: &Keyring,
+) -> Result<Option<VerifiedSignature>, String> {
+    let header = parse_header(path, content).map_err(|e| e.to_string())?;
+
+    let status = header.status.unwrap_or_default();
+    let integrity = header.integrity.as_ref();
+    let signature = integrity.and_then(|i| get_yaml_string(&i.signature));
+
+    let signature = match signature {
+        Some(s) => s,
+        None if status == "sealed" || status == "deprecated" || status == "superseded" => {
+            return Err(format!("status is '{}' but signature is missing", status));
+        }
+        None => return Ok(None),
+    };
+
+    let signer = integrity
+        .and_then(|i| get_yaml_string(&i.signed_by))
+        .ok_or("signature present but signed_by is missing")?;
+    let stored = integrity
+        .and_then(|i| get_yaml_string(&i.content_hash))
+        .ok_or("signature present but content_hash is missing")?;
+    let signed_at_raw = integrity
+        .and_then(|i| get_yaml_string(&i.signed_at))
+        .ok_or("signature present but signed_at is missing")?;
+    let signed_at = DateTime::parse_from_rfc3339(signed_at_raw)
+        .map_err(|e| format!("invalid signed_at '{}': {}", signed_at_raw, e))?
+        .with_timezone(&Utc);
+
+    let computed = seal::compute_content_hash(content).ok_or("missing YAML frontmatter")?;
+    if !stored.eq_ignore_ascii_case(&computed) {
+        return Err("content tampered: stored content_hash does not match the document body".to_string());
+    }
+
+    let sig_bytes = hex::decode(signature).map_err(|_| "signature is not valid hex".to_string())?;
+    let sig = Signature::from_slice(&sig_bytes).map_err(|e| format!("malformed signature: {}", e))?;
+    let message = signature_message(&computed, signer, signed_at_raw).map_err(|e| e.to_string())?;
+
+    let key = keyring
+        .verify(signer, signed_at, &message, &sig)
+        .map_err(|e| e.to_string())?;
+
+    keyring
+        .authorize(signer, header.authority_tier.as_deref(), get_yaml_string(&header.trust_class))
+        .map_err(|e| e.to_string())?;
+
+    Ok(Some(VerifiedSignature {
+        signer: signer.to_string(),
+        key_id: key.key_id,
+        revoked_at: key.revoked_at,
+    }))
+}
+
+/// Run the `verify-signature` command.
+pub fn verify(
+    repo_root_arg: Option<String>,
+    file_path: Option<String>,
+    keyring_path: Option<String>,
+) -> Result<(), SignError> {
+    let repo_root = match repo_root_arg {
+        Some(path) => PathBuf::from(path),
+        None => find_repo_root()?.ok_or(SignError::NoSpecsDir)?,
+    };
+
+    let keyring = keyring::load_keyring(&repo_root, keyring_path.as_deref().map(Path::new))?;
+
+    println!("=== XONAIX LIBRARY SIGNATURE VERIFICATION ===");
+    println!("Repository: {}", repo_root.display());
+    println!("Trusted Signers: {}", keyring.signers.len());
+    println!();
+
+    let mut verified = 0u32;
+    let mut revoked = 0u32;
+    let mut unsigned = 0u32;
+    let mut failed = 0u32;
+
+    for path in collect_documents(&repo_root, file_path)? {
+        let content = fs::read_to_string(&path)?;
+        match verify_document_signature(&path, &content, &keyring) {
+            Ok(Some(v)) => {
+                println!("PASS: {} (signed by {}, key {})", path.display(), v.signer, v.key_id);
+                if let Some(at) = v.revoked_at {
+                    println!(
+                        "  WARN: key {} was revoked at {} after the anchored signature, re-sign required",
+                        v.key_id,
+                        at.to_rfc3339()
+                    );
+                    revoked += 1;
+                }
+                verified += 1;
+            }
+            Ok(None) => unsigned += 1,
+            Err(reason) => {
+                println!("FAIL: {}: {}", path.display(), reason);
+                failed += 1;
+            }
+        }
+    }
+
+    println!();
+    println!("=== VERIFICATION COMPLETE ===");
+    println!("Verified: {}", verified);
+    println!("Signed by later-revoked keys: {}", revoked);
+    println!("Unsigned: {}", unsigned);
+    println!("Failed: {}", failed);
+
+    if failed > 0 {
+        Err(SignError::VerificationFailed(failed))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::keyring::tests::{test_key, test_keyring};
+
+    const BODY: &str = "# Title\n\nBody text.\n";
+
+    /// A sealed document signed by alice at `signed_at`, whose header then claims `claimed_at`.
+    pub(crate) fn signed_document(signed_at: &str, claimed_at: &str) -> String {
+        let hash = seal::compute_content_hash(&format!("---\n---\n{}", BODY)).unwrap_or_default();
+        let message = signature_message(&hash, "alice", signed_at).unwrap_or_default();
+        let signature = hex::encode(test_key().sign(&message).to_bytes());
+        format!(
+            "---\nstatus: \"sealed\"\ntrust_class: \"L1\"\nauthority_tier: \"T1\"\nintegrity:\n  hash_alg: \"SHA3-512\"\n  content_hash: \"{}\"\n  signature: \"{}\"\n  signed_by: \"alice\"\n  signed_at: \"{}\"\n---\n{}",
+            hash, signature, claimed_at, BODY
+        )
+    }
+
+    fn verify(content: &str, revoked_at: Option<&str>) -> Result<Option<VerifiedSignature>, String> {
+        verify_document_signature(Path::new("DOC.md"), content, &test_keyring(revoked_at))
+    }
+
+    #[test]
+    fn signature_message_binds_hash_signer_and_time() {
+        let message = signature_message("ABCD", "alice", "2025-02-01T00:00:00Z").unwrap_or_default();
+        assert_eq!(
+            String::from_utf8(message).unwrap_or_default(),
+            r#"{"content_hash":"abcd","domain":"xonaix-document-signature/v1","signed_at":"2025-02-01T00:00:00Z","signed_by":"alice"}"#
+        );
+    }
+
+    #[test]
+    fn verifies_signed_document() {
+        let content = signed_document("2025-02-01T00:00:00Z", "2025-02-01T00:00:00Z");
+        assert!(verify(&content, None).is_ok_and(|v| v.is_some_and(|v| v.key_id == "alice-2025")));
+    }
+
+    #[test]
+    fn rejects_tampered_body() {
+        let content = signed_document("2025-02-01T00:00:00Z", "2025-02-01T00:00:00Z").replace("Body text", "Other text");
+        assert!(verify(&content, None).is_err_and(|e| e.contains("content tampered")));
+    }
+
+    /// Regression: rewriting signed_at after signing must invalidate the signature.
+    #[test]
+    fn rejects_backdated_signed_at() {
+        let content = signed_document("2025-04-01T00:00:00Z", "2025-02-01T00:00:00Z");
+        assert!(verify(&content, None).is_err_and(|e| e.contains("does not verify")));
+    }
+
+    #[test]
+    fn reports_key_revoked_after_signing() {
+        let content = signed_document("2025-02-01T00:00:00Z", "2025-02-01T00:00:00Z");
+        let verified = verify(&content, Some("2025-03-01T00:00:00Z"));
+        assert!(verified.is_ok_and(|v| v.is_some_and(|v| v.revoked_at.is_some())));
+    }
+
+    #[test]
+    fn rejects_signature_after_revocation() {
+        let content = signed_document("2025-04-01T00:00:00Z", "2025-04-01T00:00:00Z");
+        assert!(verify(&content, Some("2025-03-01T00:00:00Z")).is_err_and(|e| e.contains("revoked")));
+    }
+
+    #[test]
+    fn rejects_unauthorized_trust_class() {
+        let content = signed_document("2025-02-01T00:00:00Z", "2025-02-01T00:00:00Z").replace("\"L1\"", "\"L4\"");
+        assert!(verify(&content, None).is_err_and(|e| e.contains("not authorized")));
+    }
+
+    #[test]
+    fn signs_only_when_authorized() {
+        let hash = seal::compute_content_hash(&format!("---\n---\n{}", BODY)).unwrap_or_default();
+        let approved = format!(
+            "---\nstatus: \"approved\"\ntrust_class: \"L1\"\nauthority_tier: \"T1\"\nintegrity:\n  hash_alg: \"SHA3-512\"\n  content_hash: \"{}\"\n  signature: null\n  signed_by: null\n  signed_at: null\n---\n{}",
+            hash, BODY
+        );
+        let path = std::env::temp_dir().join(format!("xonaix-sign-test-{}.md", std::process::id()));
+        let keyring = test_keyring(None);
+
+        let unauthorized = approved.replace("\"T1\"", "\"T0\"");
+        let _ = fs::write(&path, &unauthorized);
+        assert!(matches!(
+            sign_document(&path, &test_key(), "alice", &keyring),
+            Err(SignError::Keyring(KeyringError::Unauthorized(..)))
+        ));
+        assert_eq!(fs::read_to_string(&path).unwrap_or_default(), unauthorized);
+
+        let _ = fs::write(&path, &approved);
+        assert!(sign_document(&path, &test_key(), "alice", &keyring).is_ok());
+        let signed = fs::read_to_string(&path).unwrap_or_default();
+        let _ = fs::remove_file(&path);
+        assert!(signed.contains("status: \"sealed\""));
+        assert!(verify(&signed, None).is_ok_and(|v| v.is_some()));
+    }
+
+    /// Regression: an unauthorized document used to abort the batch after
+    /// earlier documents had already been sealed.
+    #[test]
+    fn batch_signing_continues_past_failures() {
+        let root = std::env::temp_dir().join(format!("xonaix-sign-batch-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let _ = fs::create_dir_all(root.join("specs"));
+
+        let hash = seal::compute_content_hash(&format!("---\n---\n{}", BODY)).unwrap_or_default();
+        let approved = |tier: &str| {
+            format!(
+                "---\nstatus: \"approved\"\ntrust_class: \"L1\"\nauthority_tier: \"{}\"\nintegrity:\n  hash_alg: \"SHA3-512\"\n  content_hash: \"{}\"\n  signature: null\n  signed_by: null\n  signed_at: null\n---\n{}",
+                tier, hash, BODY
+            )
+        };
+        let _ = fs::write(root.join("specs/A.md"), approved("T0"));
+        let _ = fs::write(root.join("specs/B.md"), approved("T1"));
+
+        let key_path = root.join("key.hex");
+        let _ = fs::write(&key_path, hex::encode(test_key().to_bytes()));
+        let keyring_path = root.join("keyring.json");
+        let keyring = format!(
+            r#"{{"keyring_version":"{}","signers":{{"alice":{{"authority_tiers":["T1"],"trust_classes":["L1"],"keys":[{{"key_id":"alice-2025","public_key":"{}","valid_from":"2025-01-01T00:00:00Z"}}]}}}}}}"#,
+            keyring::KEYRING_VERSION,
+            hex::encode(test_key().verifying_key().to_bytes())
+        );
+        let _ = fs::write(&keyring_path, keyring);
+
+        let result = sign(
+            Some(root.display().to_string()),
+            None,
+            key_path.display().to_string(),
+            "alice".to_string(),
+            Some(keyring_path.display().to_string()),
+        );
+        let first = fs::read_to_string(root.join("specs/A.md")).unwrap_or_default();
+        let second = fs::read_to_string(root.join("specs/B.md")).unwrap_or_default();
+        let _ = fs::remove_dir_all(&root);
+
+        assert!(matches!(result, Err(SignError::SigningFailed(1))));
+        assert_eq!(first, approved("T0"));
+        assert!(second.contains("status: \"sealed\""));
+    }
+
+    #[test]
+    fn sealed_document_requires_signature() {
+        let content = format!("---\nstatus: \"sealed\"\nintegrity:\n  signature: null\n---\n{}", BODY);
+        assert!(verify(&content, None).is_err());
+        assert!(verify(&content.replace("sealed", "draft"), None).is_ok_and(|v| v.is_none()));
+    }
+}
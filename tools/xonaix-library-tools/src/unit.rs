@@ -9,6 +9,8 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 
+use crate::common::find_repo_root;
+
 /// Errors during unit operations.
 #[derive(Debug, Error)]
 pub enum UnitError {
@@ -68,26 +70,8 @@ pub struct RegistryEntry {
     pub description: String,
 }
 
-/// Find repository root.
-fn find_repo_root() -> Result<PathBuf, UnitError> {
-    let mut current = std::env::current_dir()?;
-
-    if current.join("specs").exists() {
-        return Ok(current);
-    }
-
-    while let Some(parent) = current.parent() {
-        if parent.join("specs").exists() {
-            return Ok(parent.to_path_buf());
-        }
-        current = parent.to_path_buf();
-    }
-
-    Err(UnitError::NoSpecsDir)
-}
-
 /// Load the unit registry.
-fn load_registry(repo_root: &Path) -> Result<UnitRegistry, UnitError> {
+pub(crate) fn load_registry(repo_root: &Path) -> Result<UnitRegistry, UnitError> {
     let registry_path = repo_root.join("specs/_governance/UNIT_REGISTRY.json");
     let content = fs::read_to_string(&registry_path)?;
     let registry: UnitRegistry = serde_json::from_str(&content)?;
@@ -95,7 +79,7 @@ fn load_registry(repo_root: &Path) -> Result<UnitRegistry, UnitError> {
 }
 
 /// Load a UNIT.json file.
-fn load_unit_json(path: &Path) -> Result<UnitJson, UnitError> {
+pub(crate) fn load_unit_json(path: &Path) -> Result<UnitJson, UnitError> {
     let content = fs::read_to_string(path)?;
     let unit: UnitJson = serde_json::from_str(&content)?;
     Ok(unit)
@@ -105,7 +89,7 @@ fn load_unit_json(path: &Path) -> Result<UnitJson, UnitError> {
 pub fn validate(repo_root_arg: Option<String>, unit_path: Option<String>) -> Result<(), UnitError> {
     let repo_root = match repo_root_arg {
         Some(path) => PathBuf::from(path),
-        None => find_repo_root()?,
+        None => find_repo_root()?.ok_or(UnitError::NoSpecsDir)?,
     };
 
     println!("=== UNIT VALIDATION ===");
@@ -229,7 +213,7 @@ pub fn validate(repo_root_arg: Option<String>, unit_path: Option<String>) -> Res
 pub fn graph_verify(repo_root_arg: Option<String>) -> Result<(), UnitError> {
     let repo_root = match repo_root_arg {
         Some(path) => PathBuf::from(path),
-        None => find_repo_root()?,
+        None => find_repo_root()?.ok_or(UnitError::NoSpecsDir)?,
     };
 
     println!("=== DEPENDENCY GRAPH VERIFICATION ===");
@@ -0,0 +1,133 @@
+//! Version parsing module.
+//!
+//! Parses the prefixed `{PREFIX}-MAJOR.MINOR.PATCH` versions of header
+//! contract §5 (document versions, `XBASE` baselines) and of Library BOMs
+//! (`LIB`). Numeric parts follow semver: digits only, no leading zeros.
+//! Versions compare by number, and only when their prefixes match.
+
+use std::cmp::Ordering;
+use std::fmt;
+
+/// Document version prefixes (header contract §5.1).
+pub(crate) const DOMAIN_PREFIXES: &[&str] = &["XZERO", "XCORT", "XCODE", "XNEX", "XBLADE", "XINFRA", "XLIB", "XGOV", "XUX"];
+
+/// Baseline version prefix (header contract §5.2).
+pub(crate) const BASELINE_PREFIX: &str = "XBASE";
+
+/// Required document version prefix by repository path.
+const PREFIX_BY_PATH: &[(&str, &str)] = &[
+    ("specs/standards/", "XLIB"),
+    ("specs/meta/", "XLIB"),
+    ("specs/_governance/", "XGOV"),
+];
+
+/// A parsed `{PREFIX}-MAJOR.MINOR.PATCH` version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Version {
+    pub prefix: String,
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+}
+
+impl PartialOrd for Version {
+    /// Versions with different prefixes are not comparable.
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self.prefix != other.prefix {
+            return None;
+        }
+        Some((self.major, self.minor, self.patch).cmp(&(other.major, other.minor, other.patch)))
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}.{}.{}", self.prefix, self.major, self.minor, self.patch)
+    }
+}
+
+/// Parse a semver numeric identifier: digits only, no leading zeros.
+fn parse_number(part: &str) -> Option<u64> {
+    if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) || (part.len() > 1 && part.starts_with('0')) {
+        return None;
+    }
+    part.parse().ok()
+}
+
+/// Parse `{PREFIX}-MAJOR.MINOR.PATCH`, where the prefix is uppercase ASCII.
+pub(crate) fn parse(value: &str) -> Result<Version, String> {
+    let invalid = || format!("'{}' is not PREFIX-MAJOR.MINOR.PATCH", value);
+
+    let (prefix, numbers) = value.split_once('-').ok_or_else(invalid)?;
+    if prefix.is_empty() || !prefix.chars().all(|c| c.is_ascii_uppercase()) {
+        return Err(invalid());
+    }
+
+    let mut parts = numbers.split('.').map(parse_number);
+    match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some(Some(major)), Some(Some(minor)), Some(Some(patch)), None) => Ok(Version {
+            prefix: prefix.to_string(),
+            major,
+            minor,
+            patch,
+        }),
+        _ => Err(invalid()),
+    }
+}
+
+/// The version prefix required for a document at a repo-relative path, if any.
+pub(crate) fn expected_prefix(relative_path: &str) -> Option<&'static str> {
+    PREFIX_BY_PATH
+        .iter()
+        .find(|(dir, _)| relative_path.starts_with(dir))
+        .map(|(_, prefix)| *prefix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_prefixed_versions() {
+        let parsed = parse("XLIB-1.20.3");
+        assert_eq!(
+            parsed,
+            Ok(Version {
+                prefix: "XLIB".to_string(),
+                major: 1,
+                minor: 20,
+                patch: 3,
+            })
+        );
+        assert!(parsed.is_ok_and(|v| v.to_string() == "XLIB-1.20.3"));
+        assert!(parse("XBASE-0.0.0").is_ok());
+    }
+
+    #[test]
+    fn rejects_malformed_versions() {
+        for value in ["XLIB-01.0.0", "XLIB-1.0", "XLIB-1.0.0.0", "XLIB-1.0.x", "xlib-1.0.0", "-1.0.0", "1.0.0", "XLIB-1.-1.0", "XLIB-1.0.0-rc1"] {
+            assert!(parse(value).is_err(), "{}", value);
+        }
+    }
+
+    #[test]
+    fn compares_numerically_within_a_prefix() {
+        let older = parse("XLIB-1.9.0");
+        let newer = parse("XLIB-1.10.0");
+        assert!(older.is_ok_and(|o| newer.is_ok_and(|n| o < n)));
+    }
+
+    #[test]
+    fn different_prefixes_do_not_compare() {
+        let lib = parse("XLIB-1.0.0");
+        let gov = parse("XGOV-1.0.0");
+        assert!(lib.is_ok_and(|l| gov.is_ok_and(|g| l.partial_cmp(&g).is_none())));
+    }
+
+    #[test]
+    fn expected_prefix_by_path() {
+        assert_eq!(expected_prefix("specs/standards/sql/STANDARDS_SQL.md"), Some("XLIB"));
+        assert_eq!(expected_prefix("specs/_governance/POLICY.md"), Some("XGOV"));
+        assert_eq!(expected_prefix("README.md"), None);
+    }
+}